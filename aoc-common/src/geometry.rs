@@ -0,0 +1,302 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A 2D vector or point, as (y, x) with y increasing downwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec2 {
+    pub y: isize,
+    pub x: isize,
+}
+
+impl Vec2 {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(y: isize, x: isize) -> Self {
+        Self { y, x }
+    }
+
+    /// Manhattan distance between two points
+    pub fn manhattan(self, other: Self) -> usize {
+        self.y.abs_diff(other.y) + self.x.abs_diff(other.x)
+    }
+
+    /// Checks if the point is within a (height, width) map
+    pub fn is_within(self, (height, width): (usize, usize)) -> bool {
+        self.y >= 0 && self.x >= 0 && (self.y as usize) < height && (self.x as usize) < width
+    }
+
+    /// Converts back to an unsigned (y, x) point, if neither part is negative
+    pub fn to_point(self) -> Option<(usize, usize)> {
+        Some((self.y.try_into().ok()?, self.x.try_into().ok()?))
+    }
+}
+
+impl From<(usize, usize)> for Vec2 {
+    fn from((y, x): (usize, usize)) -> Self {
+        Self::new(y as isize, x as isize)
+    }
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from((y, x): (isize, isize)) -> Self {
+        Self::new(y, x)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.y + rhs.y, self.x + rhs.x)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.y - rhs.y, self.x - rhs.x)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.y * rhs, self.x * rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.y, -self.x)
+    }
+}
+
+/// Moves an unsigned (y, x) point by an offset, if it stays within a (height, width) map
+pub fn checked_move(
+    (y, x): (usize, usize),
+    offset: Vec2,
+    (height, width): (usize, usize),
+) -> Option<(usize, usize)> {
+    let y = y.checked_add_signed(offset.y)?;
+    let x = x.checked_add_signed(offset.x)?;
+
+    (y < height && x < width).then_some((y, x))
+}
+
+/// The 4 orthogonal directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise from up
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn turn_right(self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Left,
+            Right => Up,
+            Down => Right,
+            Left => Down,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// A single step in this direction
+    pub fn offset(self) -> Vec2 {
+        use Direction::*;
+        match self {
+            Up => Vec2::new(-1, 0),
+            Right => Vec2::new(0, 1),
+            Down => Vec2::new(1, 0),
+            Left => Vec2::new(0, -1),
+        }
+    }
+}
+
+/// The 8 compass directions, including diagonals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise from up
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Rotates 45 degrees clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Rotates 45 degrees anticlockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// A single step in this direction
+    pub fn offset(self) -> Vec2 {
+        use Direction8::*;
+        match self {
+            Up => Vec2::new(-1, 0),
+            UpRight => Vec2::new(-1, 1),
+            Right => Vec2::new(0, 1),
+            DownRight => Vec2::new(1, 1),
+            Down => Vec2::new(1, 0),
+            DownLeft => Vec2::new(1, -1),
+            Left => Vec2::new(0, -1),
+            UpLeft => Vec2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::Up => Self::Up,
+            Direction::Right => Self::Right,
+            Direction::Down => Self::Down,
+            Direction::Left => Self::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_move_boundaries() {
+        let size = (3, 4);
+        assert_eq!(checked_move((1, 1), Vec2::new(-1, -1), size), Some((0, 0)));
+        assert_eq!(checked_move((0, 2), Vec2::new(-1, 0), size), None);
+        assert_eq!(checked_move((2, 0), Vec2::new(0, -1), size), None);
+        assert_eq!(checked_move((1, 2), Vec2::new(1, 1), size), Some((2, 3)));
+        assert_eq!(checked_move((2, 3), Vec2::new(1, 0), size), None);
+        assert_eq!(checked_move((2, 3), Vec2::new(0, 1), size), None);
+        assert_eq!(checked_move((0, 0), Vec2::ZERO, size), Some((0, 0)));
+        assert_eq!(checked_move((0, 0), Vec2::ZERO, (0, 0)), None);
+        assert_eq!(
+            checked_move((usize::MAX, 0), Vec2::new(1, 0), (usize::MAX, 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn direction_rotations() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+
+    #[test]
+    fn direction8_rotations() {
+        use Direction8::*;
+
+        assert_eq!(Up.turn_right(), UpRight);
+        assert_eq!(UpLeft.turn_right(), Up);
+        assert_eq!(Up.turn_left(), UpLeft);
+        assert_eq!(DownRight.turn_left(), Right);
+        assert_eq!(UpRight.reverse(), DownLeft);
+        assert_eq!(Left.reverse(), Right);
+
+        for direction in Direction8::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(
+                direction.reverse(),
+                direction
+                    .turn_right()
+                    .turn_right()
+                    .turn_right()
+                    .turn_right()
+            );
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+        }
+        // Orthogonal directions match their 4-way versions
+        for direction in Direction::ALL {
+            assert_eq!(Direction8::from(direction).offset(), direction.offset());
+        }
+    }
+
+    #[test]
+    fn to_point() {
+        assert_eq!(Vec2::new(2, 3).to_point(), Some((2, 3)));
+        assert_eq!(Vec2::ZERO.to_point(), Some((0, 0)));
+        assert_eq!(Vec2::new(-1, 3).to_point(), None);
+        assert_eq!(Vec2::new(2, -1).to_point(), None);
+        assert_eq!(Vec2::from((4_usize, 5_usize)).to_point(), Some((4, 5)));
+    }
+
+    #[test]
+    fn manhattan() {
+        assert_eq!(Vec2::new(1, 2).manhattan(Vec2::new(4, 6)), 7);
+        assert_eq!(Vec2::new(4, 6).manhattan(Vec2::new(1, 2)), 7);
+        assert_eq!(Vec2::new(-3, 2).manhattan(Vec2::new(1, -2)), 8);
+        assert_eq!(Vec2::ZERO.manhattan(Vec2::ZERO), 0);
+    }
+
+    #[test]
+    fn is_within() {
+        assert!(Vec2::new(0, 0).is_within((1, 1)));
+        assert!(!Vec2::new(0, 0).is_within((0, 1)));
+        assert!(!Vec2::new(-1, 0).is_within((3, 3)));
+        assert!(!Vec2::new(1, 3).is_within((3, 3)));
+    }
+}
@@ -0,0 +1,286 @@
+use std::ops::Index;
+
+use thiserror::Error;
+
+use crate::{
+    geometry::{checked_move, Direction, Direction8, Vec2},
+    location::Location,
+};
+
+/// A rectangular grid of cells, indexed by (y, x) with (0, 0) in the top left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// Problems with the shape of a grid
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GridError {
+    #[error("{location}: expected a row {expected} wide, found one {found} wide")]
+    RaggedRow {
+        location: Location,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid out of rows, which must all be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(GridError::RaggedRow {
+                location: Location::new(i, row.len().min(width)),
+                expected: width,
+                found: row.len(),
+            });
+        }
+
+        Ok(Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    /// Parses a grid out of text, one line per row, converting each char into a cell
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        Self::try_parse(input, |_, c| Ok::<_, GridError>(f(c)))
+    }
+
+    /// Parses a grid out of text, one line per row, converting each char into a cell with a
+    /// function that can reject it
+    pub fn try_parse<E: From<GridError>>(
+        input: &str,
+        mut f: impl FnMut(Location, char) -> Result<T, E>,
+    ) -> Result<Self, E> {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(y, l)| {
+                l.chars()
+                    .enumerate()
+                    .map(|(x, c)| f(Location::new(y, x), c))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::from_rows(rows)?)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// (height, width) of the grid
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Checks if a possibly negative point is on the grid
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.is_within(self.size())
+    }
+
+    pub fn get(&self, (y, x): (usize, usize)) -> Option<&T> {
+        if y < self.height && x < self.width {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (y, x): (usize, usize)) -> Option<&mut T> {
+        if y < self.height && x < self.width {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Moves from a point by a signed offset, if we stay on the grid
+    pub fn offset(&self, point: (usize, usize), offset: Vec2) -> Option<(usize, usize)> {
+        checked_move(point, offset, self.size())
+    }
+
+    /// Gets the cell at a signed offset from a point, if it's on the grid
+    pub fn get_offset(&self, point: (usize, usize), offset: Vec2) -> Option<&T> {
+        self.offset(point, offset).and_then(|p| self.get(p))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Going by row rather than chunks() copes with zero width grids, which still have rows
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        // Otherwise we'd run into the next row
+        assert!(x < self.width, "Column is outside of the grid");
+        (0..self.height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /// Every (y, x) point on the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (y, x)))
+    }
+
+    /// Every cell along with its point, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// Walks from a point (inclusive) in steps of the given offset until we fall off the grid.
+    /// Rows, columns and diagonals are all rays with the appropriate step.
+    pub fn ray(
+        &self,
+        start: (usize, usize),
+        step: Vec2,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        std::iter::successors(self.get(start).map(|_| start), move |&p| {
+            self.offset(p, step)
+        })
+        .map(|p| (p, &self[p]))
+    }
+
+    /// The up to 4 orthogonal neighbours of a point
+    pub fn orthogonal_neighbours(
+        &self,
+        point: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours_by(point, Direction::ALL.map(Direction::offset))
+    }
+
+    /// The up to 8 neighbours of a point, including diagonals
+    pub fn neighbours(&self, point: (usize, usize)) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours_by(point, Direction8::ALL.map(Direction8::offset))
+    }
+
+    fn neighbours_by<const N: usize>(
+        &self,
+        point: (usize, usize),
+        offsets: [Vec2; N],
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        offsets
+            .into_iter()
+            .filter_map(move |offset| self.offset(point, offset))
+            .map(|p| (p, &self[p]))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: (usize, usize)) -> &Self::Output {
+        self.get(point).expect("Point is outside of the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// abcd
+    /// efgh
+    /// ijkl
+    fn grid() -> Grid<char> {
+        Grid::parse("abcd\nefgh\nijkl\n", |c| c).unwrap()
+    }
+
+    /// The cells along an iterator of points and cells
+    fn cells<'a>(points: impl Iterator<Item = ((usize, usize), &'a char)>) -> String {
+        points.map(|(_, c)| c).collect()
+    }
+
+    #[test]
+    fn ray() {
+        let grid = grid();
+        assert_eq!(cells(grid.ray((0, 1), Vec2::new(0, 1))), "bcd");
+        assert_eq!(cells(grid.ray((0, 0), Vec2::new(1, 1))), "afk");
+        assert_eq!(cells(grid.ray((2, 3), Vec2::new(-1, -1))), "lgb");
+        assert_eq!(cells(grid.ray((0, 3), Vec2::new(1, -1))), "dgj");
+        // Starting off the grid gives nothing, rather than walking back onto it
+        assert_eq!(cells(grid.ray((3, 0), Vec2::new(-1, 0))), "");
+        assert_eq!(cells(grid.ray((0, 4), Vec2::new(0, -1))), "");
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        assert_eq!(cells(grid.neighbours((0, 0))), "bfe");
+        assert_eq!(cells(grid.neighbours((2, 3))), "hkg");
+        assert_eq!(cells(grid.neighbours((1, 1))), "bcgkjiea");
+        assert_eq!(cells(grid.orthogonal_neighbours((0, 3))), "hc");
+        assert_eq!(cells(grid.orthogonal_neighbours((2, 0))), "ej");
+    }
+
+    #[test]
+    fn get_offset() {
+        let grid = grid();
+        assert_eq!(grid.get_offset((1, 1), Vec2::new(-1, -1)), Some(&'a'));
+        assert_eq!(grid.get_offset((2, 3), Vec2::new(-2, -3)), Some(&'a'));
+        assert_eq!(grid.get_offset((0, 1), Vec2::new(-1, 0)), None);
+        assert_eq!(grid.get_offset((1, 0), Vec2::new(0, -1)), None);
+        assert_eq!(grid.get_offset((1, 3), Vec2::new(0, 1)), None);
+        assert_eq!(grid.offset((0, 0), Vec2::new(isize::MIN, 0)), None);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows()
+                .map(|r| r.iter().collect())
+                .collect::<Vec<String>>(),
+            ["abcd", "efgh", "ijkl"]
+        );
+        assert_eq!(grid.column(2).collect::<String>(), "cgk");
+        assert_eq!(grid.size(), (3, 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Column is outside of the grid")]
+    fn column_outside() {
+        let _ = grid().column(4);
+    }
+
+    #[test]
+    fn zero_width_rows() {
+        let grid = Grid::<char>::from_rows(vec![vec![], vec![]]).unwrap();
+        assert_eq!(grid.size(), (2, 0));
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [&[] as &[char], &[]]);
+        assert_eq!(Grid::<char>::parse("", |c| c).unwrap().rows().count(), 0);
+    }
+
+    #[test]
+    fn ragged_row() {
+        assert_eq!(
+            Grid::parse("abc\nde\nfgh\n", |c| c).unwrap_err(),
+            GridError::RaggedRow {
+                location: Location::new(1, 2),
+                expected: 3,
+                found: 2,
+            }
+        );
+        // Too long points at the first char which doesn't fit
+        assert_eq!(
+            Grid::parse("abc\nabc\ndefg\n", |c| c).unwrap_err(),
+            GridError::RaggedRow {
+                location: Location::new(2, 3),
+                expected: 3,
+                found: 4,
+            }
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! Helpers shared between the days
+
+pub mod geometry;
+pub mod grid;
+pub mod location;
+pub mod logging;
+
+pub use geometry::{Direction, Direction8, Vec2};
+pub use grid::{Grid, GridError};
+pub use location::Location;
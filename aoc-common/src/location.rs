@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+/// Where something is in the puzzle input, both 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Location from a 0-based line index and char index, as given by `enumerate()`
+    pub fn new(line_index: usize, char_index: usize) -> Self {
+        Self {
+            line: line_index + 1,
+            column: char_index + 1,
+        }
+    }
+
+    /// Locates a token which was sliced out of the given line, eg. by `split()`
+    pub fn of_token(line_index: usize, line: &str, token: &str) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&offset| offset <= line.len())
+            .expect("Token must be a slice of the line");
+
+        Self::new(line_index, line[..offset].chars().count())
+    }
+
+    /// Locates a byte offset into the whole input
+    pub fn of_offset(input: &str, offset: usize) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self::new(
+            before.matches('\n').count(),
+            before[line_start..].chars().count(),
+        )
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn of_token() {
+        let line = "ab é 12";
+        let token = line.split(' ').nth(2).unwrap();
+        assert_eq!(Location::of_token(4, line, token), Location::new(4, 5));
+        assert_eq!(Location::of_token(0, line, &line[..0]), Location::new(0, 0));
+        assert_eq!(
+            Location::of_token(0, line, &line[line.len()..]),
+            Location::new(0, 7)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Token must be a slice of the line")]
+    fn of_token_elsewhere() {
+        // An owned copy, as string literals can end up sharing memory
+        let token = "34".to_string();
+        Location::of_token(0, "12 34", &token);
+    }
+
+    #[test]
+    fn of_offset() {
+        let input = "ab\néx\n\nyz";
+        assert_eq!(Location::of_offset(input, 0), Location::new(0, 0));
+        assert_eq!(Location::of_offset(input, 2), Location::new(0, 2));
+        // é is two bytes but one column
+        assert_eq!(Location::of_offset(input, 5), Location::new(1, 1));
+        assert_eq!(Location::of_offset(input, 7), Location::new(2, 0));
+        assert_eq!(Location::of_offset(input, 9), Location::new(3, 1));
+    }
+}
@@ -0,0 +1,26 @@
+use std::fmt::Write;
+
+use rand::Rng;
+
+/// Rows in a real puzzle input
+pub const PUZZLE_SIZE: usize = 1000;
+
+/// Generates two columns of 5 digit location IDs. About half of the right list is copied from the
+/// left one, so the similarity score isn't always zero
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let left = (0..size)
+        .map(|_| rng.random_range(10000..100000))
+        .collect::<Vec<u64>>();
+
+    let mut input = String::with_capacity(size * 14);
+    for &l in &left {
+        let r = if rng.random_bool(0.5) {
+            left[rng.random_range(0..size)]
+        } else {
+            rng.random_range(10000..100000)
+        };
+        writeln!(input, "{}   {}", l, r).unwrap();
+    }
+
+    input
+}
@@ -0,0 +1,47 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+use rand::Rng;
+
+/// Reports in a real puzzle input
+pub const PUZZLE_SIZE: usize = 1000;
+
+/// Generates reports of 5 to 8 levels. Each one starts out safe, then gets some of its levels
+/// knocked out of place so there's a mix of safe, dampenable and unsafe reports
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut input = String::with_capacity(size * 24);
+    for _ in 0..size {
+        let length = rng.random_range(5..=8);
+        let increasing = rng.random_bool(0.5);
+
+        // Start far enough from 0 that decreasing reports stay positive
+        let mut level = rng.random_range(30..70);
+        let mut report = (0..length)
+            .map(|_| {
+                let current = level;
+                let step = rng.random_range(1..=3);
+                level = if increasing {
+                    level + step
+                } else {
+                    level - step
+                };
+                current
+            })
+            .collect::<Vec<u64>>();
+
+        // Most reports get zero or one bad level, a few get more
+        let bad_levels = match rng.random_range(0..10) {
+            0..4 => 0,
+            4..8 => 1,
+            _ => 2,
+        };
+        for _ in 0..bad_levels {
+            let i = rng.random_range(0..length);
+            report[i] = rng.random_range(1..100);
+        }
+
+        writeln!(input, "{}", report.iter().join(" ")).unwrap();
+    }
+
+    input
+}
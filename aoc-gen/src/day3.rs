@@ -0,0 +1,80 @@
+use std::fmt::Write;
+
+use rand::{seq::IndexedRandom, Rng};
+
+use crate::Generated;
+
+/// Commands in a real puzzle input
+pub const PUZZLE_SIZE: usize = 750;
+
+/// Garbage which looks a lot like commands, but isn't one
+const NOISE: [&str; 18] = [
+    "mul[3,7]",
+    "mul(32,64]",
+    "mul ( 2 , 4 )",
+    "mul(4*",
+    "mul(6,9!",
+    "?(12,34)",
+    "mul(1234,5)",
+    "mul(,)",
+    "select()",
+    "from()",
+    "who()",
+    "what()",
+    "where()",
+    "how()",
+    "don't",
+    "do(",
+    "undo",
+    "xmul",
+];
+
+/// Characters which can sit between commands without joining up with them
+const SEPARATORS: &[u8] = b"!@#$%^&*[]<>+-_?{}~ /:;";
+
+/// Generates corrupted memory with `size` real commands planted in garbage. The answers to both
+/// parts are worked out while planting, so they're known without running a solution
+pub fn generate(rng: &mut impl Rng, size: usize) -> Generated {
+    let mut input = String::with_capacity(size * 40);
+    let mut answers = [0, 0];
+    let mut enabled = true;
+
+    for i in 0..size {
+        // Surround every command with separated noise
+        for _ in 0..rng.random_range(0..=3) {
+            input.push(*SEPARATORS.choose(rng).unwrap() as char);
+            input.push_str(NOISE.choose(rng).unwrap());
+        }
+        input.push(*SEPARATORS.choose(rng).unwrap() as char);
+
+        match rng.random_range(0..10) {
+            0 => {
+                input.push_str("do()");
+                enabled = true;
+            }
+            1 => {
+                input.push_str("don't()");
+                enabled = false;
+            }
+            _ => {
+                let (a, b) = (rng.random_range(0..1000), rng.random_range(0..1000));
+                write!(input, "mul({},{})", a, b).unwrap();
+                answers[0] += a * b;
+                if enabled {
+                    answers[1] += a * b;
+                }
+            }
+        }
+
+        // Split the memory over a few lines, like the real input
+        if i % 128 == 127 {
+            input.push('\n');
+        }
+    }
+    input.push('\n');
+
+    Generated {
+        input,
+        answers: Some(answers),
+    }
+}
@@ -0,0 +1,15 @@
+use rand::{seq::IndexedRandom, Rng};
+
+/// Side of a real puzzle input
+pub const PUZZLE_SIZE: usize = 140;
+
+/// Generates a square grid of random letters from XMAS
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut input = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        input.extend((0..size).map(|_| *b"XMAS".choose(rng).unwrap() as char));
+        input.push('\n');
+    }
+
+    input
+}
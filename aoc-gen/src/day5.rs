@@ -0,0 +1,57 @@
+use std::{collections::HashMap, fmt::Write};
+
+use itertools::Itertools;
+use rand::{
+    seq::{IndexedRandom, SliceRandom},
+    Rng,
+};
+
+/// Updates in a real puzzle input
+pub const PUZZLE_SIZE: usize = 200;
+
+/// How many different pages there are, like the real input
+const PAGES: usize = 49;
+
+/// Generates ordering rules from a random total order of the pages, so the rules can never form a
+/// cycle and every pair of pages in an update has a rule. Around half of the updates are already in
+/// order
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut pages = (10..100).collect::<Vec<u64>>();
+    pages.shuffle(rng);
+    pages.truncate(PAGES);
+    let rank = pages
+        .iter()
+        .enumerate()
+        .map(|(i, &page)| (page, i))
+        .collect::<HashMap<_, _>>();
+
+    let mut rules = pages
+        .iter()
+        .tuple_combinations::<(_, _)>()
+        .collect::<Vec<_>>();
+    rules.shuffle(rng);
+
+    let mut input = String::new();
+    for (before, after) in rules {
+        writeln!(input, "{}|{}", before, after).unwrap();
+    }
+    input.push('\n');
+
+    for _ in 0..size {
+        // Updates always have a middle page
+        let length = rng.random_range(2..=11) * 2 + 1;
+        let mut update = pages
+            .choose_multiple(rng, length)
+            .copied()
+            .collect::<Vec<_>>();
+        if rng.random_bool(0.5) {
+            update.sort_by_key(|page| rank[page]);
+        } else {
+            update.shuffle(rng);
+        }
+
+        writeln!(input, "{}", update.iter().join(",")).unwrap();
+    }
+
+    input
+}
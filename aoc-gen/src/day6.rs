@@ -0,0 +1,167 @@
+use rand::Rng;
+
+/// Side of a real puzzle input
+pub const PUZZLE_SIZE: usize = 130;
+
+/// Chance of any tile being an obstacle, about the same as the real input
+const OBSTACLE_DENSITY: f64 = 0.05;
+
+/// Generates a square map with scattered obstacles and the guard facing up from a random empty
+/// tile. Obstacles which would send the guard round in circles are cleared, and guards who walk
+/// straight off without touching much of the map are put down somewhere else
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut tiles = (0..size * size)
+        .map(|_| {
+            if rng.random_bool(OBSTACLE_DENSITY) {
+                b'#'
+            } else {
+                b'.'
+            }
+        })
+        .collect::<Vec<_>>();
+
+    loop {
+        let guard = rng.random_range(0..tiles.len());
+        if tiles[guard] == b'#' || patrol(&mut tiles, size, guard) < size {
+            continue;
+        }
+
+        tiles[guard] = b'^';
+        return tiles
+            .chunks(size)
+            .map(|row| String::from_utf8_lossy(row) + "\n")
+            .collect();
+    }
+}
+
+/// Walks the guard from `guard` until they leave the map, giving how many tiles they visited.
+/// Whenever they'd go round in circles, the last obstacle they turned at is cleared and the walk
+/// picks up from where they first ran into it, so they always get out
+fn patrol(tiles: &mut [u8], size: usize, guard: usize) -> usize {
+    // Up, right, down, left, so turning right is the next one along
+    const OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+    // The tile in front of the guard, if it's still on the map
+    let ahead = |tile: usize, facing: usize| {
+        let (dy, dx) = OFFSETS[facing];
+        let y = (tile / size).checked_add_signed(dy)?;
+        let x = (tile % size).checked_add_signed(dx)?;
+        (y < size && x < size).then_some(y * size + x)
+    };
+
+    // Every (tile, facing) so far, with a bit for each direction the guard has faced on each tile
+    let mut path = vec![(guard, 0)];
+    let mut seen = vec![0_u8; tiles.len()];
+    seen[guard] = 1;
+    // Where along the path each obstacle was first run into, in the order they were
+    let mut first_hit = vec![usize::MAX; tiles.len()];
+    let mut hits = vec![];
+
+    while let Some(&(tile, facing)) = path.last() {
+        let Some(next) = ahead(tile, facing) else {
+            break;
+        };
+        let (next, turned) = if tiles[next] == b'#' {
+            if first_hit[next] == usize::MAX {
+                first_hit[next] = path.len() - 1;
+                hits.push(next);
+            }
+            ((tile, (facing + 1) % 4), true)
+        } else {
+            ((next, facing), false)
+        };
+
+        if seen[next.0] & 1 << next.1 == 0 {
+            seen[next.0] |= 1 << next.1;
+            path.push(next);
+            continue;
+        }
+
+        // Going round in circles, so the last turn was part of the loop
+        let obstacle = if turned {
+            ahead(tile, facing)
+        } else {
+            path.windows(2)
+                .rev()
+                .find(|w| w[0].0 == w[1].0)
+                .and_then(|w| ahead(w[0].0, w[0].1))
+        }
+        .expect("Loops always turn");
+        tiles[obstacle] = b'.';
+
+        // Go back to where the obstacle was first run into, forgetting everything since
+        let rewind = first_hit[obstacle];
+        while let Some(&hit) = hits.last() {
+            if first_hit[hit] < rewind {
+                break;
+            }
+            first_hit[hit] = usize::MAX;
+            hits.pop();
+        }
+        for (tile, facing) in path.drain(rewind + 1..) {
+            seen[tile] &= !(1 << facing);
+        }
+    }
+
+    seen.iter().filter(|&&s| s != 0).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    /// Walks the guard for at most as many steps as there are positions and facings, which is as
+    /// long as any walk off the map can take. Kept separate from `patrol` so it checks it
+    fn leaves_map(input: &str) -> bool {
+        let rows = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let (height, width) = (rows.len() as isize, rows[0].len() as isize);
+        let (mut y, mut x) = rows
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| Some((y as isize, row.iter().position(|&c| c == b'^')? as isize)))
+            .unwrap();
+        let (mut dy, mut dx) = (-1, 0);
+
+        for _ in 0..=4 * height * width {
+            let (ny, nx) = (y + dy, x + dx);
+            if !(0..height).contains(&ny) || !(0..width).contains(&nx) {
+                return true;
+            }
+            if rows[ny as usize][nx as usize] == b'#' {
+                (dy, dx) = (dx, -dy);
+            } else {
+                (y, x) = (ny, nx);
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn guard_always_leaves() {
+        for size in [5, 13, 40, PUZZLE_SIZE] {
+            for seed in 0..100 {
+                let input = generate(&mut ChaCha8Rng::seed_from_u64(seed), size);
+                assert!(leaves_map(&input), "size {} seed {}", size, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn patrol_breaks_loops() {
+        let mut tiles = *b".#...\
+                           ....#\
+                           .....\
+                           #....\
+                           ...#.";
+        // The guard goes round the four obstacles until the last one they turned at is cleared
+        assert_eq!(patrol(&mut tiles, 5, 16), 9);
+        assert_eq!(tiles.iter().filter(|&&c| c == b'#').count(), 3);
+        assert_eq!(tiles[15], b'.');
+
+        let mut tiles = *b".........";
+        assert_eq!(patrol(&mut tiles, 3, 4), 2);
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+use rand::Rng;
+
+/// Equations in a real puzzle input
+pub const PUZZLE_SIZE: usize = 850;
+
+/// Most digits the operands of one equation can have between them. Concatenating everything is
+/// the biggest result any mix of operators can give, so this keeps the totals within a u64
+const MAX_DIGITS: u32 = 18;
+
+/// Generates equations of 2 to 10 operands. Around half of the totals come from applying random
+/// operators, the rest are nudged off that so they probably can't be made
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let count = rng.random_range(2..=10);
+        let mut digits = 0;
+        let operands = (0..count)
+            .map_while(|_| {
+                let operand = rng.random_range(1..1000_u64);
+                digits += operand.ilog10() + 1;
+                (digits <= MAX_DIGITS).then_some(operand)
+            })
+            .collect::<Vec<_>>();
+
+        let mut total =
+            operands[1..]
+                .iter()
+                .fold(operands[0], |acc, &x| match rng.random_range(0..3) {
+                    0 => acc + x,
+                    1 => acc * x,
+                    _ => acc * 10_u64.pow(x.ilog10() + 1) + x,
+                });
+        if rng.random_bool(0.5) {
+            total += rng.random_range(1..10);
+        }
+
+        writeln!(input, "{}: {}", total, operands.iter().join(" ")).unwrap();
+    }
+
+    input
+}
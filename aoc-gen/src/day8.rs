@@ -0,0 +1,27 @@
+use rand::{
+    seq::{IndexedRandom, SliceRandom},
+    Rng,
+};
+
+/// Side of a real puzzle input
+pub const PUZZLE_SIZE: usize = 50;
+
+/// Characters an antenna's frequency can be
+const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Generates a square map with antennae on about 1 in 12 tiles, like the real input
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut tiles = vec![b'.'; size * size];
+    let antennae = tiles.len() / 12;
+
+    // Put the antennae on the first tiles then shuffle them around, so none land on each other
+    for tile in &mut tiles[..antennae] {
+        *tile = *FREQUENCIES.choose(rng).unwrap();
+    }
+    tiles.shuffle(rng);
+
+    tiles
+        .chunks(size)
+        .map(|row| String::from_utf8_lossy(row) + "\n")
+        .collect()
+}
@@ -0,0 +1,18 @@
+use rand::Rng;
+
+/// Files in a real puzzle input
+pub const PUZZLE_SIZE: usize = 10000;
+
+/// Generates a disk map of `size` files of 1 to 9 blocks, with gaps of 0 to 9 blocks between them
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut input = String::with_capacity(size * 2);
+    for i in 0..size {
+        if i > 0 {
+            input.push(char::from(b'0' + rng.random_range(0..=9)));
+        }
+        input.push(char::from(b'0' + rng.random_range(1..=9)));
+    }
+    input.push('\n');
+
+    input
+}
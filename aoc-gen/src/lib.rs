@@ -0,0 +1,93 @@
+//! Seeded generators for synthetic puzzle inputs, for stress testing and benchmarking on inputs
+//! far bigger than the real ones. The same day, seed and size always give the same input.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// A generated input, along with its answers if the generator knows them up front
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub answers: Option<[u64; 2]>,
+}
+
+impl From<String> for Generated {
+    fn from(input: String) -> Self {
+        Self {
+            input,
+            answers: None,
+        }
+    }
+}
+
+pub struct Generator {
+    pub day: u8,
+    /// What the size knob controls, for help text
+    pub size_unit: &'static str,
+    /// Size that gives an input roughly as big as the real puzzle input
+    pub puzzle_size: usize,
+    /// Whether the size is the side of a square grid rather than a count of lines
+    pub is_grid: bool,
+    generate: fn(&mut ChaCha8Rng, usize) -> Generated,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: usize) -> Generated {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        (self.generate)(&mut rng, size)
+    }
+
+    /// The size giving an input `factor` times as big as the real one. Grids grow in both
+    /// directions, so their side only grows by the square root
+    pub fn scaled_size(&self, factor: f64) -> usize {
+        let factor = if self.is_grid { factor.sqrt() } else { factor };
+        ((self.puzzle_size as f64 * factor).round() as usize).max(1)
+    }
+}
+
+/// Wraps a day's generator into a [`Generator`]
+macro_rules! generator {
+    ($day:literal, $krate:ident, $unit:literal, grid) => {
+        generator!(@ $day, $krate, $unit, true)
+    };
+    ($day:literal, $krate:ident, $unit:literal) => {
+        generator!(@ $day, $krate, $unit, false)
+    };
+    (@ $day:literal, $krate:ident, $unit:literal, $is_grid:literal) => {
+        Generator {
+            day: $day,
+            size_unit: $unit,
+            puzzle_size: $krate::PUZZLE_SIZE,
+            is_grid: $is_grid,
+            generate: |rng, size| $krate::generate(rng, size).into(),
+        }
+    };
+}
+
+/// Every day's generator, in order of day
+pub const GENERATORS: &[Generator] = &[
+    generator!(1, day1, "rows"),
+    generator!(2, day2, "reports"),
+    generator!(3, day3, "commands"),
+    generator!(4, day4, "tiles per side", grid),
+    generator!(5, day5, "updates"),
+    generator!(6, day6, "tiles per side", grid),
+    generator!(7, day7, "equations"),
+    generator!(8, day8, "tiles per side", grid),
+    generator!(9, day9, "files"),
+];
+
+/// Looks up the generator for a day
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
@@ -0,0 +1,44 @@
+//! Times day 1 end to end, parsing and both parts, on lists far bigger than the puzzle's, to
+//! compare the fast path against the original one. Run with `cargo bench -p aoc --bench
+//! day1_pipeline`.
+
+use std::{hint::black_box, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Rows in each generated input
+const ROWS: [usize; 2] = [100_000, 1_000_000];
+
+fn bench_pipeline(c: &mut Criterion) {
+    let generator = aoc_gen::generator(1).expect("Day 1 has a generator");
+
+    let mut group = c.benchmark_group("day1/pipeline");
+    for rows in ROWS {
+        let input = generator.generate(2024, rows).input;
+        group.throughput(Throughput::Elements(rows as u64));
+
+        group.bench_with_input(BenchmarkId::new("default", rows), &input, |b, input| {
+            b.iter(|| {
+                let input = day1::parse(black_box(input)).unwrap();
+                (day1::solve_part1(&input), day1::solve_part2(&input))
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("fast", rows), &input, |b, input| {
+            b.iter(|| {
+                let input = day1::parse_fast(black_box(input)).unwrap();
+                (day1::solve_part1(&input), day1::solve_part2_fast(&input))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(5));
+    targets = bench_pipeline
+}
+criterion_main!(benches);
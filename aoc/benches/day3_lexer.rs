@@ -0,0 +1,42 @@
+//! Times picking the commands out of day 3 inputs far bigger than the puzzle's, comparing the
+//! regex with the lexer, both over the whole input and streamed in chunks. Run with `cargo bench
+//! -p aoc --bench day3_lexer`.
+
+use std::{hint::black_box, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Sizes of the generated inputs, in puzzle inputs
+const SCALES: [f64; 2] = [10.0, 100.0];
+
+fn bench_lexer(c: &mut Criterion) {
+    let generator = aoc_gen::generator(3).expect("Day 3 has a generator");
+
+    let mut group = c.benchmark_group("day3/lexer");
+    for scale in SCALES {
+        let input = generator.generate(2024, generator.scaled_size(scale)).input;
+        let name = format!("x{}", scale);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("regex", &name), &input, |b, input| {
+            b.iter(|| day3::parse(black_box(input)).unwrap().len())
+        });
+        group.bench_with_input(BenchmarkId::new("lexer", &name), &input, |b, input| {
+            b.iter(|| day3::lex(black_box(input.as_bytes())).count())
+        });
+        group.bench_with_input(BenchmarkId::new("stream", &name), &input, |b, input| {
+            b.iter(|| day3::commands(black_box(input.as_bytes())).count())
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(5));
+    targets = bench_lexer
+}
+criterion_main!(benches);
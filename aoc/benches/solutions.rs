@@ -0,0 +1,92 @@
+//! Times parsing and solving separately for every day, part and variant.
+//!
+//! Each day is benchmarked on its real input from `inputs/dayN/input.txt` if there is one, and on
+//! synthetic inputs from `aoc-gen` at a few multiples of the real input's size. Filter down to
+//! what you're comparing with eg. `cargo bench -p aoc -- day2/part2`.
+
+use std::{fs, hint::black_box, path::Path, time::Duration};
+
+use aoc::solutions::SOLUTIONS;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Seed for the synthetic inputs, fixed so runs can be compared
+const SEED: u64 = 2024;
+
+/// How big each synthetic input is compared to a real puzzle input
+const SCALES: [f64; 3] = [0.25, 1.0, 4.0];
+
+/// The inputs to benchmark a day on, as (name, input)
+fn inputs(day: u8) -> Vec<(String, String)> {
+    let generator = aoc_gen::generator(day).expect("Every day has a generator");
+
+    let real = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(format!("../inputs/day{}/input.txt", day))
+        .canonicalize()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|input| ("real".to_string(), input));
+
+    real.into_iter()
+        .chain(SCALES.iter().map(|&scale| {
+            let size = generator.scaled_size(scale);
+            (format!("x{}", scale), generator.generate(SEED, size).input)
+        }))
+        .collect()
+}
+
+fn bench_solutions(c: &mut Criterion) {
+    let mut days = SOLUTIONS.iter().map(|s| s.day).collect::<Vec<_>>();
+    days.dedup();
+
+    for day in days {
+        let inputs = inputs(day);
+        let solutions = SOLUTIONS
+            .iter()
+            .filter(|s| s.day == day)
+            .collect::<Vec<_>>();
+
+        // Parts mostly share a parser, so only time the first part's variants which each might
+        // parse their own way
+        let mut group = c.benchmark_group(format!("day{}/parse", day));
+        for solution in solutions.iter().filter(|s| s.part == solutions[0].part) {
+            for (name, input) in &inputs {
+                group.throughput(Throughput::Bytes(input.len() as u64));
+                group.bench_with_input(
+                    BenchmarkId::new(solution.variant, name),
+                    input,
+                    |b, input| {
+                        b.iter_with_large_drop(|| (solution.parse)(black_box(input)).unwrap())
+                    },
+                );
+            }
+        }
+        group.finish();
+
+        for solution in solutions {
+            let mut group = c.benchmark_group(format!(
+                "day{}/part{}/{}",
+                solution.day, solution.part, solution.variant
+            ));
+            for (name, input) in &inputs {
+                let parsed = (solution.parse)(input).unwrap();
+
+                group.throughput(Throughput::Bytes(input.len() as u64));
+                group.bench_with_input(BenchmarkId::from_parameter(name), &parsed, |b, parsed| {
+                    b.iter(|| (solution.solve)(black_box(&**parsed)))
+                });
+            }
+            group.finish();
+        }
+    }
+}
+
+criterion_group! {
+    name = benches;
+    // There's a lot of benchmarks, so keep each one short
+    config = Criterion::default()
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2));
+    targets = bench_solutions
+}
+criterion_main!(benches);
@@ -0,0 +1,348 @@
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use aoc::solutions::{Solution, SOLUTIONS};
+use aoc_gen::GENERATORS;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code 2024 solutions")]
+struct Cli {
+    /// Log more detail to stderr, repeat for more. `RUST_LOG` overrides this
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the solutions for a day, or every day
+    Run {
+        /// Day to run (1-9), or `all`
+        target: Target,
+        /// Only run this part
+        #[arg(long)]
+        part: Option<u8>,
+        /// Run this variant instead of the default, eg. `dumb` for day 2 part 2
+        #[arg(long)]
+        variant: Option<String>,
+        /// Input file to use for a single day, `-` to read stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Directory holding the `dayN/input.txt` inputs
+        #[arg(long, default_value = "inputs")]
+        inputs: PathBuf,
+        /// How to print the results
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Generates a synthetic input for a day
+    Generate {
+        /// Day to generate an input for (1-9)
+        day: u8,
+        /// Seed for the generator, the same seed always gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// How big to make the input, in the day's own units. Defaults to about the size of the
+        /// real puzzle input
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        size: Option<u64>,
+        /// Scale the size by this much instead of setting it directly
+        #[arg(long, conflicts_with = "size")]
+        scale: Option<f64>,
+        /// File to write the input to instead of stdout. Any known answers are written next to it
+        /// as a `.expected` file
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// How results get printed
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// A table for people to read
+    Table,
+    /// One JSON object per line for each solution run
+    Json,
+}
+
+/// Which days to run
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    All,
+    Day(u8),
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Target::All),
+            _ => s
+                .parse::<u8>()
+                .map(Target::Day)
+                .map_err(|_| format!("expected a day number or `all`, got {:?}", s)),
+        }
+    }
+}
+
+/// Reads a puzzle input from a file, or stdin if the path is `-`
+fn read_input(path: &Path) -> Result<String, String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("Failed to read stdin: {}", e))?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    }
+}
+
+/// One row of the summary table
+struct Run {
+    solution: &'static Solution,
+    answer: String,
+    parse_time: Duration,
+    solve_time: Duration,
+}
+
+/// A [`Run`] as it gets written out in JSON
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: u8,
+    variant: &'a str,
+    answer: &'a str,
+    parse_time_ns: u128,
+    solve_time_ns: u128,
+}
+
+impl<'a> From<&'a Run> for Record<'a> {
+    fn from(run: &'a Run) -> Self {
+        Self {
+            day: run.solution.day,
+            part: run.solution.part,
+            variant: run.solution.variant,
+            answer: &run.answer,
+            parse_time_ns: run.parse_time.as_nanos(),
+            solve_time_ns: run.solve_time.as_nanos(),
+        }
+    }
+}
+
+fn print_table(runs: &[Run]) {
+    let answer_width = runs
+        .iter()
+        .map(|r| r.answer.len())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:>3}  {:>4}  {:<11}  {:>answer_width$}  {:>10}  {:>10}",
+        "Day", "Part", "Variant", "Answer", "Parse", "Solve"
+    );
+    for run in runs {
+        println!(
+            "{:>3}  {:>4}  {:<11}  {:>answer_width$}  {:>10}  {:>10}",
+            run.solution.day,
+            run.solution.part,
+            run.solution.variant,
+            run.answer,
+            format!("{:.2?}", run.parse_time),
+            format!("{:.2?}", run.solve_time)
+        );
+    }
+
+    let total = runs
+        .iter()
+        .map(|r| r.parse_time + r.solve_time)
+        .sum::<Duration>();
+    println!("Total time: {:.2?}", total);
+}
+
+fn print_json(runs: &[Run]) {
+    for run in runs {
+        println!(
+            "{}",
+            serde_json::to_string(&Record::from(run)).expect("Records always serialise")
+        );
+    }
+}
+
+fn run(
+    target: Target,
+    part: Option<u8>,
+    variant: Option<&str>,
+    input: Option<&Path>,
+    inputs: &Path,
+    format: Format,
+) -> Result<(), String> {
+    if input.is_some() && matches!(target, Target::All) {
+        return Err("--input can only be used when running a single day".to_string());
+    }
+
+    // Pick out the solutions we've been asked for
+    let selected = SOLUTIONS
+        .iter()
+        .filter(|s| match target {
+            Target::All => true,
+            Target::Day(day) => s.day == day,
+        })
+        .filter(|s| part.is_none_or(|part| s.part == part))
+        .filter(|s| match variant {
+            Some(variant) => s.variant == variant,
+            None => s.is_default(),
+        })
+        .collect::<Vec<_>>();
+    if selected.is_empty() {
+        return Err(format!(
+            "No solutions found for {:?}, part {:?}, variant {:?}",
+            target, part, variant
+        ));
+    }
+
+    let mut runs = vec![];
+    for day_solutions in selected.chunk_by(|a, b| a.day == b.day) {
+        let day = day_solutions[0].day;
+
+        // Only read each day's input once
+        let path = input
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| inputs.join(format!("day{}", day)).join("input.txt"));
+        let text = match read_input(&path) {
+            Ok(text) => text,
+            // Missing inputs are skipped when running everything
+            Err(e) if matches!(target, Target::All) => {
+                eprintln!("Skipping day {}: {}", day, e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        for &solution in day_solutions {
+            let start = Instant::now();
+            let parsed = (solution.parse)(&text)
+                .map_err(|e| format!("Failed to parse day {} input: {}", day, e))?;
+            let parse_time = start.elapsed();
+
+            let start = Instant::now();
+            let answer = (solution.solve)(&*parsed).map_err(|e| {
+                format!("Failed to solve day {} part {}: {}", day, solution.part, e)
+            })?;
+            let solve_time = start.elapsed();
+
+            runs.push(Run {
+                solution,
+                answer,
+                parse_time,
+                solve_time,
+            });
+        }
+    }
+
+    match format {
+        Format::Table => print_table(&runs),
+        Format::Json => print_json(&runs),
+    }
+
+    Ok(())
+}
+
+fn generate(
+    day: u8,
+    seed: u64,
+    size: Option<u64>,
+    scale: Option<f64>,
+    output: Option<&Path>,
+) -> Result<(), String> {
+    let generator = aoc_gen::generator(day).ok_or_else(|| {
+        format!(
+            "No generator for day {}, expected one of {:?}",
+            day,
+            GENERATORS.iter().map(|g| g.day).collect::<Vec<_>>()
+        )
+    })?;
+    let size = match (size, scale) {
+        (Some(size), _) => size as usize,
+        (None, Some(scale)) => generator.scaled_size(scale),
+        (None, None) => generator.puzzle_size,
+    };
+    eprintln!(
+        "Generating day {} with seed {} and {} {}",
+        day, seed, size, generator.size_unit
+    );
+
+    let generated = generator.generate(seed, size);
+    let answers = generated
+        .answers
+        .map(|[part1, part2]| format!("{}\n{}\n", part1, part2));
+
+    match output {
+        Some(path) => {
+            fs::write(path, &generated.input)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            if let Some(answers) = answers {
+                let expected = path.with_extension("expected");
+                fs::write(&expected, answers)
+                    .map_err(|e| format!("Failed to write {}: {}", expected.display(), e))?;
+            }
+        }
+        None => {
+            print!("{}", generated.input);
+            if let Some(answers) = answers {
+                eprint!("Answers:\n{}", answers);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    aoc_common::logging::init(cli.verbose);
+
+    let result = match &cli.command {
+        Command::Run {
+            target,
+            part,
+            variant,
+            input,
+            inputs,
+            format,
+        } => run(
+            *target,
+            *part,
+            variant.as_deref(),
+            input.as_deref(),
+            inputs,
+            *format,
+        ),
+        Command::Generate {
+            day,
+            seed,
+            size,
+            scale,
+            output,
+        } => generate(*day, *seed, *size, *scale, output.as_deref()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
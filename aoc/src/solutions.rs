@@ -0,0 +1,109 @@
+use std::{any::Any, error::Error, fmt::Display};
+
+/// A day's parsed input, with its type erased so every day fits in the same table
+pub type Parsed = Box<dyn Any>;
+
+/// A day's answer, or why it couldn't be worked out
+pub type SolveResult = Result<String, Box<dyn Error>>;
+
+/// A single way of solving one part of a day
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    pub variant: &'static str,
+    /// Parses the input into whatever the day uses, failing if the input is malformed
+    pub parse: fn(&str) -> Result<Parsed, Box<dyn Error>>,
+    /// Solves an input which came out of `parse`, failing if the answer can't be worked out
+    pub solve: fn(&dyn Any) -> SolveResult,
+}
+
+impl Solution {
+    /// The default variant is the first one listed for its part
+    pub fn is_default(&self) -> bool {
+        SOLUTIONS
+            .iter()
+            .find(|s| s.day == self.day && s.part == self.part)
+            .is_some_and(|s| s.variant == self.variant)
+    }
+
+    /// Parses and solves the input in one go
+    pub fn run(&self, input: &str) -> SolveResult {
+        (self.solve)(&*(self.parse)(input)?)
+    }
+}
+
+/// Gets a parsed input back out of its box, using the day's parse function to pin down the type
+fn downcast<T: 'static, E>(_parse: fn(&str) -> Result<T, E>, input: &dyn Any) -> &T {
+    input
+        .downcast_ref()
+        .expect("Input was parsed by a different day")
+}
+
+/// Anything a solve function can give back
+trait Answer {
+    fn into_answer(self) -> SolveResult;
+}
+
+impl Answer for u64 {
+    fn into_answer(self) -> SolveResult {
+        Ok(self.to_string())
+    }
+}
+
+impl Answer for usize {
+    fn into_answer(self) -> SolveResult {
+        Ok(self.to_string())
+    }
+}
+
+impl<T: Display, E: Error + 'static> Answer for Result<T, E> {
+    fn into_answer(self) -> SolveResult {
+        Ok(self?.to_string())
+    }
+}
+
+macro_rules! solution {
+    ($day:literal, $part:literal, $variant:literal, $krate:ident::$solve:ident) => {
+        solution!($day, $part, $variant, $krate::parse => $solve)
+    };
+    // For variants which parse the input their own way
+    ($day:literal, $part:literal, $variant:literal, $krate:ident::$parse:ident => $solve:ident) => {
+        Solution {
+            day: $day,
+            part: $part,
+            variant: $variant,
+            parse: |input| Ok(Box::new($krate::$parse(input)?)),
+            solve: |input| {
+                let input = downcast($krate::$parse, input);
+                $krate::$solve(input).into_answer()
+            },
+        }
+    };
+}
+
+/// Every solution we have, in day/part order. The first variant listed for a part is its default.
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(1, 1, "default", day1::solve_part1),
+    solution!(1, 1, "fast", day1::parse_fast => solve_part1),
+    solution!(1, 2, "default", day1::solve_part2),
+    solution!(1, 2, "fast", day1::parse_fast => solve_part2_fast),
+    solution!(2, 1, "default", day2::solve_part1),
+    solution!(2, 2, "smort", day2::solve_part2_smort),
+    solution!(2, 2, "dumb", day2::solve_part2),
+    solution!(3, 1, "default", day3::solve_part1),
+    solution!(3, 1, "lexer", day3::parse_lexer => solve_part1),
+    solution!(3, 2, "default", day3::solve_part2),
+    solution!(3, 2, "lexer", day3::parse_lexer => solve_part2),
+    solution!(4, 1, "default", day4::solve_part1),
+    solution!(4, 2, "default", day4::solve_part2),
+    solution!(5, 1, "default", day5::solve_part1),
+    solution!(5, 2, "default", day5::solve_part2),
+    solution!(6, 1, "default", day6::solve_part1),
+    solution!(6, 2, "brute_force", day6::solve_part2),
+    solution!(7, 1, "default", day7::solve_part1),
+    solution!(7, 2, "default", day7::solve_part2),
+    solution!(8, 1, "default", day8::solve_part1),
+    solution!(8, 2, "default", day8::solve_part2),
+    solution!(9, 1, "default", day9::solve_part1),
+    solution!(9, 2, "default", day9::solve_part2),
+];
@@ -0,0 +1,39 @@
+//! Checks that every generator makes inputs its day can solve, and that planted answers are right
+
+use aoc::solutions::SOLUTIONS;
+use aoc_gen::GENERATORS;
+
+#[test]
+fn generated_inputs_solve() {
+    for generator in GENERATORS {
+        for seed in 0..4 {
+            let generated = generator.generate(seed, generator.scaled_size(0.1));
+            let answers = SOLUTIONS
+                .iter()
+                .filter(|s| s.day == generator.day && s.is_default())
+                .map(|s| s.run(&generated.input).unwrap())
+                .collect::<Vec<_>>();
+
+            if let Some(expected) = generated.answers {
+                assert_eq!(
+                    answers,
+                    expected.map(|a| a.to_string()),
+                    "day {} seed {}",
+                    generator.day,
+                    seed
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn generators_are_deterministic() {
+    for generator in GENERATORS {
+        assert_eq!(generator.generate(7, 10), generator.generate(7, 10));
+        assert_ne!(
+            generator.generate(7, 10).input,
+            generator.generate(8, 10).input
+        );
+    }
+}
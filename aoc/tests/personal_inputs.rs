@@ -0,0 +1,61 @@
+//! Checks personal puzzle inputs against their known answers.
+//!
+//! This is opt-in: put inputs in `inputs/dayN/<name>.txt` at the workspace root, next to a
+//! `<name>.expected` file holding one answer per line, part 1 first. Inputs without an
+//! `.expected` file and blank answer lines are skipped. `inputs/` is git-ignored, so nothing in
+//! there ever gets committed.
+
+use std::{fs, path::Path};
+
+use aoc::solutions::SOLUTIONS;
+
+#[test]
+fn personal_inputs() {
+    let inputs = Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs");
+
+    let mut days = SOLUTIONS.iter().map(|s| s.day).collect::<Vec<_>>();
+    days.dedup();
+
+    let mut failures = vec![];
+    for day in days {
+        let Ok(entries) = fs::read_dir(inputs.join(format!("day{}", day))) else {
+            continue;
+        };
+
+        for path in entries.map(|e| e.expect("Failed to read inputs dir").path()) {
+            if path.extension().is_none_or(|ext| ext != "txt") {
+                continue;
+            }
+            let Ok(expected) = fs::read_to_string(path.with_extension("expected")) else {
+                continue;
+            };
+            let input = fs::read_to_string(&path).expect("Failed to read input");
+
+            // Line N of the expected file holds the answer to part N
+            for (part, expected) in (1..).zip(expected.lines().map(str::trim)) {
+                if expected.is_empty() {
+                    continue;
+                }
+
+                for solution in SOLUTIONS
+                    .iter()
+                    .filter(|s| s.day == day && s.part == part && s.is_default())
+                {
+                    match solution.run(&input) {
+                        Ok(answer) if answer == expected => {}
+                        Ok(answer) => failures.push(format!(
+                            "{} part {}: expected {}, got {}",
+                            path.display(),
+                            part,
+                            expected,
+                            answer
+                        )),
+                        Err(e) => failures.push(format!("{}: {}", path.display(), e)),
+                    }
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
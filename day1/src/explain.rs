@@ -0,0 +1,152 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use crate::{Id, Input, OverflowError};
+
+/// One sorted pair, and what it adds to each score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair<T> {
+    /// Where the pair is in the sorted lists, from 0
+    pub rank: usize,
+    pub left: T,
+    pub right: T,
+    /// What the pair adds to the total distance
+    pub distance: T,
+    /// How many times the left ID shows up in the right list
+    pub multiplicity: usize,
+}
+
+/// What one distinct left ID adds to the similarity score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contribution<T> {
+    pub id: T,
+    /// How many times the ID is in the left list
+    pub left_count: usize,
+    /// How many times it's in the right list
+    pub right_count: usize,
+    pub similarity: T,
+}
+
+/// A breakdown of where both scores come from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation<T> {
+    pub pairs: Vec<Pair<T>>,
+    /// One per distinct left ID, in order of ID
+    pub contributions: Vec<Contribution<T>>,
+}
+
+impl<T: Id> Explanation<T> {
+    /// The `k` pairs furthest apart, biggest first
+    pub fn top_distances(&self, k: usize) -> Vec<&Pair<T>> {
+        let mut pairs = self.pairs.iter().collect::<Vec<_>>();
+        pairs.sort_by_key(|p| Reverse(p.distance));
+        pairs.truncate(k);
+        pairs
+    }
+
+    /// The `k` left IDs adding the most to the similarity score, biggest first
+    pub fn top_similarities(&self, k: usize) -> Vec<&Contribution<T>> {
+        let mut contributions = self.contributions.iter().collect::<Vec<_>>();
+        contributions.sort_by_key(|c| Reverse(c.similarity));
+        contributions.truncate(k);
+        contributions
+    }
+}
+
+/// Breaks both scores down into what each pair and each left ID adds
+pub fn explain<T: Id>(input: &Input<T>) -> Result<Explanation<T>, OverflowError> {
+    let mut right_counts = HashMap::new();
+    for &x in &input.list2 {
+        *right_counts.entry(x).or_insert(0) += 1;
+    }
+    let right_count = |x| right_counts.get(&x).copied().unwrap_or(0);
+
+    let pairs = input
+        .list1
+        .iter()
+        .zip(&input.list2)
+        .enumerate()
+        .map(|(rank, (&left, &right))| {
+            Ok(Pair {
+                rank,
+                left,
+                right,
+                distance: left
+                    .checked_distance(right)
+                    .ok_or_else(|| OverflowError::new::<T>("total distance"))?,
+                multiplicity: right_count(left),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // The left list is sorted, so copies of an ID are all next to each other
+    let contributions = input
+        .list1
+        .chunk_by(|a, b| a == b)
+        .map(|copies| {
+            let id = copies[0];
+            let similarity = id
+                .checked_mul_count(right_count(id))
+                .and_then(|x| x.checked_mul_count(copies.len()))
+                .ok_or_else(|| OverflowError::new::<T>("similarity score"))?;
+
+            Ok(Contribution {
+                id,
+                left_count: copies.len(),
+                right_count: right_count(id),
+                similarity,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Explanation {
+        pairs,
+        contributions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn example_breakdown() {
+        let explanation = explain(&parse(include_str!("../example.txt")).unwrap()).unwrap();
+
+        let distances = explanation
+            .pairs
+            .iter()
+            .map(|p| p.distance)
+            .collect::<Vec<_>>();
+        assert_eq!(distances, [2, 1, 0, 1, 2, 5]);
+        let multiplicities = explanation
+            .pairs
+            .iter()
+            .map(|p| p.multiplicity)
+            .collect::<Vec<_>>();
+        assert_eq!(multiplicities, [0, 0, 3, 3, 3, 1]);
+
+        let top = explanation.top_distances(2);
+        assert_eq!((top[0].left, top[0].right), (4, 9));
+        assert_eq!(top[1].rank, 0);
+
+        let top = explanation.top_similarities(1);
+        assert_eq!(
+            *top[0],
+            Contribution {
+                id: 3,
+                left_count: 3,
+                right_count: 3,
+                similarity: 27
+            }
+        );
+        assert_eq!(
+            explanation
+                .contributions
+                .iter()
+                .map(|c| c.similarity)
+                .sum::<u64>(),
+            31
+        );
+    }
+}
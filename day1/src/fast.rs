@@ -0,0 +1,173 @@
+use crate::{parse, Id, Input, OverflowError, ParseError};
+
+/// Below this many IDs a comparison sort wins, since radix sort has to clear its counts each pass
+const RADIX_THRESHOLD: usize = 256;
+
+/// Parses a number from the start of some bytes, returning it and how many bytes it took. Gives up
+/// on anything which might overflow, rather than checking every step
+fn parse_number(bytes: &[u8]) -> Option<(u64, usize)> {
+    let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    if digits == 0 || digits > 19 {
+        return None;
+    }
+
+    let number = bytes[..digits]
+        .iter()
+        .fold(0, |acc, &b| acc * 10 + (b - b'0') as u64);
+    Some((number, digits))
+}
+
+/// Counts the spaces and tabs at the start of some bytes
+fn blanks(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .take_while(|&&b| b == b' ' || b == b'\t')
+        .count()
+}
+
+/// Reads two columns of IDs straight from bytes, without building any strings or splitting lines
+/// first. Only well formed input takes the fast path, anything odd gets handed to [`parse`] so it
+/// gets exactly the same result and errors
+pub fn parse_fast(input: &str) -> Result<Input, ParseError> {
+    let bytes = input.as_bytes();
+    // A rough guess from the usual 5 digit IDs, to save most of the reallocating
+    let mut list1 = Vec::with_capacity(bytes.len() / 14);
+    let mut list2 = Vec::with_capacity(bytes.len() / 14);
+
+    let mut i = 0;
+    while i < bytes.len() {
+        i += blanks(&bytes[i..]);
+        // Skip blank lines
+        match bytes.get(i..i + 2) {
+            Some(b"\r\n") => {
+                i += 2;
+                continue;
+            }
+            _ if bytes.get(i) == Some(&b'\n') => {
+                i += 1;
+                continue;
+            }
+            _ if i == bytes.len() => break,
+            _ => (),
+        }
+
+        let Some((a, length)) = parse_number(&bytes[i..]) else {
+            return parse(input);
+        };
+        i += length;
+        let gap = blanks(&bytes[i..]);
+        let Some((b, length)) = parse_number(&bytes[i + gap..]).filter(|_| gap > 0) else {
+            return parse(input);
+        };
+        i += gap + length;
+        i += blanks(&bytes[i..]);
+        i += usize::from(bytes.get(i) == Some(&b'\r'));
+        match bytes.get(i) {
+            Some(b'\n') => i += 1,
+            None => (),
+            Some(_) => return parse(input),
+        }
+
+        list1.push(a);
+        list2.push(b);
+    }
+
+    radix_sort(&mut list1);
+    radix_sort(&mut list2);
+
+    Ok(Input { list1, list2 })
+}
+
+/// Sorts with a least significant digit radix sort, a byte at a time. Bytes which are the same for
+/// every ID get skipped, which for real inputs is most of them
+pub fn radix_sort(ids: &mut Vec<u64>) {
+    if ids.len() < RADIX_THRESHOLD {
+        ids.sort_unstable();
+        return;
+    }
+
+    let mut buffer = vec![0; ids.len()];
+    for shift in (0..u64::BITS).step_by(8) {
+        let digit = |x: u64| (x >> shift) as usize & 0xff;
+
+        let mut counts = [0; 256];
+        for &x in ids.iter() {
+            counts[digit(x)] += 1;
+        }
+        if counts.contains(&ids.len()) {
+            continue;
+        }
+
+        // Work out where each digit's IDs start, then deal them out in order
+        let mut offsets = [0; 256];
+        for d in 1..256 {
+            offsets[d] = offsets[d - 1] + counts[d - 1];
+        }
+        for &x in ids.iter() {
+            buffer[offsets[digit(x)]] = x;
+            offsets[digit(x)] += 1;
+        }
+        std::mem::swap(ids, &mut buffer);
+    }
+}
+
+/// The similarity score worked out by walking both sorted lists together, instead of counting the
+/// right list into a hash map
+pub fn solve_part2_fast<T: Id>(input: &Input<T>) -> Result<T, OverflowError> {
+    let overflow = || OverflowError::new::<T>("similarity score");
+    let right = &input.list2;
+
+    let mut j = 0;
+    input
+        .list1
+        .chunk_by(|a, b| a == b)
+        .try_fold(T::ZERO, |total, copies| {
+            let x = copies[0];
+            while j < right.len() && right[j] < x {
+                j += 1;
+            }
+            let start = j;
+            while j < right.len() && right[j] == x {
+                j += 1;
+            }
+
+            let score = x
+                .checked_mul_count(j - start)
+                .and_then(|s| s.checked_mul_count(copies.len()))
+                .ok_or_else(overflow)?;
+            total.checked_add(score).ok_or_else(overflow)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solve_part2;
+
+    #[test]
+    fn matches_slow_path() {
+        let inputs = [
+            include_str!("../example.txt"),
+            "1\t2\r\n\r\n  3 4  \n",
+            "1   2   3\n",
+            "1   x\n",
+            "1   2\n3\n",
+            "99999999999999999999   1\n",
+        ];
+        for input in inputs {
+            assert_eq!(parse_fast(input), parse(input), "{:?}", input);
+        }
+
+        // Enough IDs to go through the radix sort, with repeats and every byte in use
+        let mut seed = 7_u64;
+        let input = (0..5000)
+            .map(|_| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                format!("{}   {}\n", seed % 1000, seed >> 20)
+            })
+            .collect::<String>();
+        let fast = parse_fast(&input).unwrap();
+        assert_eq!(fast, parse(&input).unwrap());
+        assert_eq!(solve_part2_fast(&fast), solve_part2(&fast));
+    }
+}
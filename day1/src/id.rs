@@ -0,0 +1,105 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    str::FromStr,
+};
+
+use thiserror::Error;
+
+/// A location ID type. All the arithmetic is checked, since IDs can be big enough that the scores
+/// don't fit
+pub trait Id: Copy + Ord + Hash + Debug + Display + FromStr + Send + Sync + 'static {
+    /// Name to show in errors
+    const NAME: &'static str;
+    const ZERO: Self;
+
+    /// How far apart two IDs are
+    fn checked_distance(self, other: Self) -> Option<Self>;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    /// The ID added up `count` times
+    fn checked_mul_count(self, count: usize) -> Option<Self>;
+}
+
+macro_rules! impl_id {
+    ($($ty:ty),*) => {$(
+        impl Id for $ty {
+            const NAME: &'static str = stringify!($ty);
+            const ZERO: Self = 0;
+
+            fn checked_distance(self, other: Self) -> Option<Self> {
+                Self::try_from(self.abs_diff(other)).ok()
+            }
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$ty>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$ty>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$ty>::checked_mul(self, other)
+            }
+
+            fn checked_mul_count(self, count: usize) -> Option<Self> {
+                <$ty>::checked_mul(self, Self::try_from(count).ok()?)
+            }
+        }
+    )*};
+}
+
+impl_id!(u64, i64, i128);
+
+/// A score which got too big for its ID type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("the {score} doesn't fit in {id_type}")]
+pub struct OverflowError {
+    pub score: &'static str,
+    pub id_type: &'static str,
+}
+
+impl OverflowError {
+    pub fn new<T: Id>(score: &'static str) -> Self {
+        Self {
+            score,
+            id_type: T::NAME,
+        }
+    }
+}
+
+/// Which type to read IDs as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdType {
+    U64,
+    I64,
+    I128,
+}
+
+impl IdType {
+    /// Picks the narrowest type which can hold every token, preferring unsigned. Anything that
+    /// isn't a number at all is left for the parser to complain about
+    pub fn detect<'a>(tokens: impl IntoIterator<Item = &'a str>) -> Self {
+        let (mut negative, mut above_i64, mut wide) = (false, false, false);
+        for token in tokens {
+            match token.parse::<i128>() {
+                Ok(x) if x < 0 => negative = true,
+                _ => (),
+            }
+            if token.parse::<i64>().is_err() {
+                match token.parse::<u64>() {
+                    Ok(_) => above_i64 = true,
+                    Err(_) => wide |= token.parse::<i128>().is_ok(),
+                }
+            }
+        }
+
+        match (negative, above_i64, wide) {
+            (_, _, true) | (true, true, _) => IdType::I128,
+            (true, false, false) => IdType::I64,
+            (false, _, false) => IdType::U64,
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use aoc_common::Location;
+use thiserror::Error;
+
+mod explain;
+mod fast;
+mod id;
+mod metric;
+mod online;
+
+pub use explain::{explain, Contribution, Explanation, Pair};
+pub use fast::{parse_fast, radix_sort, solve_part2_fast};
+pub use id::{Id, IdType, OverflowError};
+pub use metric::{metrics, Metric, Score};
+pub use online::{Online, OrderedMultiset, Update, UpdateError};
+
+/// The two location lists, both sorted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input<T = u64> {
+    pub list1: Vec<T>,
+    pub list2: Vec<T>,
+}
+
+/// Every column of location IDs, in input order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table<T = u64> {
+    pub columns: Vec<Vec<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("{location}: expected a location ID, found {found:?}")]
+    InvalidId { location: Location, found: String },
+    #[error("{location}: expected a row of {expected} IDs, found {found}")]
+    RaggedRow {
+        location: Location,
+        expected: usize,
+        found: usize,
+    },
+    #[error("expected at least {expected} columns, found {found}")]
+    TooFewColumns { expected: usize, found: usize },
+    #[error("expected both lists to be the same length, found {left} and {right} IDs")]
+    MismatchedLengths { left: usize, right: usize },
+}
+
+impl<T: Id> Table<T> {
+    /// Pulls out two columns to compare, sorted. A table without any rows has no columns, but
+    /// any of them would just be empty, so that's what we give
+    pub fn pair(&self, left: usize, right: usize) -> Result<Input<T>, ParseError> {
+        let column = |i: usize| {
+            if self.columns.is_empty() {
+                return Ok(vec![]);
+            }
+            self.columns
+                .get(i)
+                .cloned()
+                .ok_or(ParseError::TooFewColumns {
+                    expected: left.max(right) + 1,
+                    found: self.columns.len(),
+                })
+        };
+        let mut list1 = column(left)?;
+        let mut list2 = column(right)?;
+        if list1.len() != list2.len() {
+            return Err(ParseError::MismatchedLengths {
+                left: list1.len(),
+                right: list2.len(),
+            });
+        }
+        list1.sort();
+        list2.sort();
+
+        Ok(Input { list1, list2 })
+    }
+
+    /// Scores every pair of columns against each other, as `matrix[left][right]`
+    pub fn matrix<R>(&self, score: impl Fn(&Input<T>) -> R) -> Result<Vec<Vec<R>>, ParseError> {
+        (0..self.columns.len())
+            .map(|left| {
+                (0..self.columns.len())
+                    .map(|right| Ok(score(&self.pair(left, right)?)))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Parses any number of whitespace separated columns of location IDs, as whichever ID type is asked
+/// for. Every row needs as many IDs as the first one, and blank lines are skipped
+pub fn parse_table<T: Id>(input: &str) -> Result<Table<T>, ParseError> {
+    let mut columns = Vec::<Vec<T>>::new();
+    for (i, l) in input.lines().enumerate() {
+        let row = l.split_whitespace().collect::<Vec<_>>();
+        if row.is_empty() {
+            continue;
+        }
+        if columns.is_empty() {
+            columns = vec![vec![]; row.len()];
+        }
+        if row.len() != columns.len() {
+            // Point at the first extra ID, or the end of the line if some are missing
+            let location = match row.get(columns.len()) {
+                Some(extra) => Location::of_token(i, l, extra),
+                None => Location::new(i, l.chars().count()),
+            };
+            return Err(ParseError::RaggedRow {
+                location,
+                expected: columns.len(),
+                found: row.len(),
+            });
+        }
+
+        for (column, x) in columns.iter_mut().zip(row) {
+            let id = x.parse::<T>().map_err(|_| ParseError::InvalidId {
+                location: Location::of_token(i, l, x),
+                found: x.to_string(),
+            })?;
+            column.push(id);
+        }
+    }
+
+    Ok(Table { columns })
+}
+
+/// Parses one line of a stream of updates: `+ a b` or just `a b` inserts a pair, and `- a b`
+/// removes one. Blank lines give nothing
+pub fn parse_update<T: Id>(line_index: usize, line: &str) -> Result<Option<Update<T>>, ParseError> {
+    let mut tokens = line.split_whitespace().collect::<Vec<_>>();
+    let insert = match tokens.first() {
+        None => return Ok(None),
+        Some(&"-") => false,
+        Some(&"+") => true,
+        Some(_) => {
+            tokens.insert(0, "+");
+            true
+        }
+    };
+
+    let &[_, a, b] = &tokens[..] else {
+        let location = match tokens.get(3) {
+            Some(extra) => Location::of_token(line_index, line, extra),
+            None => Location::new(line_index, line.chars().count()),
+        };
+        return Err(ParseError::RaggedRow {
+            location,
+            expected: 2,
+            found: tokens.len() - 1,
+        });
+    };
+    let parse_id = |x: &str| {
+        x.parse::<T>().map_err(|_| ParseError::InvalidId {
+            location: Location::of_token(line_index, line, x),
+            found: x.to_string(),
+        })
+    };
+    let (a, b) = (parse_id(a)?, parse_id(b)?);
+
+    Ok(Some(if insert {
+        Update::Insert(a, b)
+    } else {
+        Update::Remove(a, b)
+    }))
+}
+
+/// Parses the input into the two location lists, from the first two columns
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_table(input)?.pair(0, 1)
+}
+
+pub fn solve_part1<T: Id>(input: &Input<T>) -> Result<T, OverflowError> {
+    let overflow = || OverflowError::new::<T>("total distance");
+
+    // Calculate the total
+    input
+        .list1
+        .iter()
+        .zip(&input.list2)
+        .try_fold(T::ZERO, |total, (&a, &b)| {
+            total
+                .checked_add(a.checked_distance(b).ok_or_else(overflow)?)
+                .ok_or_else(overflow)
+        })
+}
+
+pub fn solve_part2<T: Id>(input: &Input<T>) -> Result<T, OverflowError> {
+    let overflow = || OverflowError::new::<T>("similarity score");
+
+    // Count values in right list
+    let counts = input.list2.iter().fold(HashMap::new(), |mut acc, x| {
+        *acc.entry(x).or_insert(0) += 1;
+
+        acc
+    });
+
+    // Calculate the similarity score
+    input.list1.iter().try_fold(T::ZERO, |total, x| {
+        let score = x
+            .checked_mul_count(counts.get(x).copied().unwrap_or(0))
+            .ok_or_else(overflow)?;
+        total.checked_add(score).ok_or_else(overflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(&parse(EXAMPLE).unwrap()), Ok(11));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(&parse(EXAMPLE).unwrap()), Ok(31));
+    }
+
+    #[test]
+    fn any_whitespace_and_columns() {
+        let table = parse_table::<u64>("3\t4  1\n4 3\t\t2\n").unwrap();
+        assert_eq!(table.columns, [vec![3, 4], vec![4, 3], vec![1, 2]]);
+
+        let input = table.pair(2, 0).unwrap();
+        assert_eq!((input.list1, input.list2), (vec![1, 2], vec![3, 4]));
+        assert_eq!(
+            table.matrix(solve_part1).unwrap(),
+            [
+                [Ok(0), Ok(0), Ok(4)],
+                [Ok(0), Ok(0), Ok(4)],
+                [Ok(4), Ok(4), Ok(0)]
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_input() {
+        for input in ["", "\n  \n"] {
+            let input = parse(input).unwrap();
+            assert_eq!(solve_part1(&input), Ok(0));
+            assert_eq!(solve_part2(&input), Ok(0));
+        }
+
+        // Only a table with rows can be short of columns
+        assert_eq!(
+            parse("1\n2\n"),
+            Err(ParseError::TooFewColumns {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let Err(ParseError::RaggedRow { location, .. }) = parse("1   2\n3   4   5\n6   7\n") else {
+            panic!("Expected a ragged row error");
+        };
+        assert_eq!(location, Location { line: 2, column: 9 });
+
+        let Err(ParseError::RaggedRow { location, .. }) = parse("1   2\n3\n") else {
+            panic!("Expected a ragged row error");
+        };
+        assert_eq!(location, Location { line: 2, column: 2 });
+
+        let table = Table::<u64> {
+            columns: vec![vec![1, 2], vec![3]],
+        };
+        assert_eq!(
+            table.pair(0, 1),
+            Err(ParseError::MismatchedLengths { left: 2, right: 1 })
+        );
+    }
+
+    #[test]
+    fn signed_and_wide_ids() {
+        let input = "-3   4\n-1   -3\n";
+        assert_eq!(IdType::detect(input.split_whitespace()), IdType::I64);
+        assert!(parse_table::<u64>(input).is_err());
+
+        let input = parse_table::<i64>(input).unwrap().pair(0, 1).unwrap();
+        assert_eq!(solve_part1(&input), Ok(5));
+        assert_eq!(solve_part2(&input), Ok(-3));
+
+        let wide = format!("{}   -1\n", u64::MAX);
+        assert_eq!(IdType::detect(wide.split_whitespace()), IdType::I128);
+        let input = parse_table::<i128>(&wide).unwrap().pair(0, 1).unwrap();
+        assert_eq!(solve_part1(&input), Ok(u64::MAX as i128 + 1));
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let input = parse(&format!("{}   0\n{}   0\n", u64::MAX, u64::MAX)).unwrap();
+        assert_eq!(
+            solve_part1(&input),
+            Err(OverflowError::new::<u64>("total distance"))
+        );
+
+        let input = parse_table::<i64>(&format!("{}   {}\n", i64::MIN, i64::MAX))
+            .unwrap()
+            .pair(0, 1)
+            .unwrap();
+        assert!(solve_part1(&input).is_err());
+    }
+}
@@ -1,45 +1,237 @@
-use std::collections::HashMap;
-
-fn main() {
-    // Parse input strings
-    let pairs = std::io::stdin()
-        .lines()
-        .map(Result::unwrap)
-        .map(|l| {
-            l.split("   ")
-                .map(|x| u64::from_str_radix(x, 10).unwrap())
-                .collect::<Vec<_>>()
+use std::{
+    fmt::Display,
+    io::{BufRead, Read},
+    process::ExitCode,
+};
+
+use clap::{builder::PossibleValuesParser, Parser, ValueEnum};
+use day1::{Explanation, Id, IdType, Online};
+
+#[derive(Parser)]
+#[command(about = "Compares two columns of location IDs read from stdin")]
+struct Args {
+    /// The two columns to compare, counting from 1
+    #[arg(long, num_args = 2, value_names = ["LEFT", "RIGHT"], default_values_t = [1, 2],
+          value_parser = clap::value_parser!(u64).range(1..))]
+    columns: Vec<u64>,
+    /// Compare every pair of columns instead, printing a matrix for each score
+    #[arg(long, conflicts_with = "columns")]
+    matrix: bool,
+    /// Type to read the IDs as. `auto` picks the narrowest one that fits every ID, or i128 when
+    /// reading updates online
+    #[arg(long, value_enum, default_value_t = Ids::Auto)]
+    ids: Ids,
+    /// Metrics to score the lists with, in the order to print them
+    #[arg(long, value_delimiter = ',', default_values = ["distance", "similarity"],
+          value_parser = PossibleValuesParser::new(metric_names()))]
+    metric: Vec<String>,
+    /// Read a stream of `+ a b` / `- a b` updates instead, printing the scores after each one
+    #[arg(long, conflicts_with_all = ["matrix", "columns", "metric"])]
+    online: bool,
+    /// Print each sorted pair's distance and multiplicity, and the biggest contributors to both
+    /// scores, before the scores themselves
+    #[arg(long, conflicts_with = "matrix")]
+    explain: bool,
+    /// How many of the biggest contributors to list with --explain
+    #[arg(long, default_value_t = 5, requires = "explain")]
+    top: usize,
+}
+
+fn metric_names() -> Vec<&'static str> {
+    day1::metrics::<u64>().iter().map(|m| m.name()).collect()
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Ids {
+    Auto,
+    U64,
+    I64,
+    I128,
+}
+
+/// Prints a score, or why it couldn't be worked out
+fn score<T: Display, E: Display>(score: &Result<T, E>) -> String {
+    match score {
+        Ok(score) => score.to_string(),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+fn print_matrix<T: Display, E: Display>(name: &str, matrix: &[Vec<Result<T, E>>]) {
+    println!("{}:", name);
+    for row in matrix {
+        let row = row
+            .iter()
+            .map(|x| format!("{:>12}", score(x)))
+            .collect::<Vec<_>>();
+        println!("{}", row.join(" "));
+    }
+}
+
+fn run<T: Id>(input: &str, args: &Args) -> ExitCode {
+    let table = match day1::parse_table::<T>(input) {
+        Ok(table) => table,
+        Err(e) => {
+            eprintln!("Failed to parse input: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Look the metrics up in the order they were asked for
+    let all_metrics = day1::metrics::<T>();
+    let metrics = args
+        .metric
+        .iter()
+        .map(|name| {
+            all_metrics
+                .iter()
+                .find(|m| m.name() == name)
+                .expect("Metric names are checked by clap")
         })
-        .flatten()
         .collect::<Vec<_>>();
 
-    // Pull apart the two lists
-    let mut list1 = pairs.iter().step_by(2).copied().collect::<Vec<_>>();
-    let mut list2 = pairs.iter().skip(1).step_by(2).copied().collect::<Vec<_>>();
-    list1.sort();
-    list2.sort();
+    if args.matrix {
+        for metric in metrics {
+            let matrix = table
+                .matrix(|input| metric.score(input))
+                .expect("Every column exists");
+            print_matrix(metric.label(), &matrix);
+        }
+        return ExitCode::SUCCESS;
+    }
 
-    // Calculate the total
-    let total = list1
-        .iter()
-        .zip(&list2)
-        .map(|(&a, &b)| a.abs_diff(b))
-        .sum::<u64>();
+    let input = match table.pair(args.columns[0] as usize - 1, args.columns[1] as usize - 1) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to parse input: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.explain {
+        match day1::explain(&input) {
+            Ok(explanation) => print_explanation(&explanation, args.top),
+            Err(e) => {
+                eprintln!("Failed to explain the scores: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
 
-    println!("Total: {}", total);
+    let mut exit_code = ExitCode::SUCCESS;
+    for metric in metrics {
+        let result = metric.score(&input);
+        println!("{}: {}", metric.label(), score(&result));
+        if result.is_err() {
+            exit_code = ExitCode::FAILURE;
+        }
+    }
 
-    // Count values in right list
-    let counts = list2.iter().fold(HashMap::new(), |mut acc, x| {
-        *acc.entry(x).or_insert(0) += 1;
+    exit_code
+}
 
-        acc
-    });
+fn print_explanation<T: Id>(explanation: &Explanation<T>, top: usize) {
+    println!(
+        "{:>8}  {:>12}  {:>12}  {:>12}  {:>7}",
+        "Rank", "Left", "Right", "Distance", "Matches"
+    );
+    for pair in &explanation.pairs {
+        println!(
+            "{:>8}  {:>12}  {:>12}  {:>12}  {:>7}",
+            pair.rank + 1,
+            pair.left,
+            pair.right,
+            pair.distance,
+            pair.multiplicity
+        );
+    }
 
-    // Calculate the similarity score
-    let similarity_score = list1
-        .iter()
-        .map(|x| x * counts.get(x).unwrap_or(&0))
-        .sum::<u64>();
+    println!();
+    println!("Top {} by distance:", top);
+    for pair in explanation.top_distances(top) {
+        println!(
+            "  rank {}: {} and {} are {} apart",
+            pair.rank + 1,
+            pair.left,
+            pair.right,
+            pair.distance
+        );
+    }
+
+    println!();
+    println!("Top {} by similarity:", top);
+    for contribution in explanation.top_similarities(top) {
+        println!(
+            "  {} x {} on the left x {} on the right = {}",
+            contribution.id,
+            contribution.left_count,
+            contribution.right_count,
+            contribution.similarity
+        );
+    }
+    println!();
+}
+
+/// Applies updates from stdin as they arrive. Updates which can't be applied are reported and
+/// skipped
+fn run_online<T: Id>() -> ExitCode {
+    let mut online = Online::<T>::new();
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for (i, line) in std::io::stdin().lock().lines().enumerate() {
+        let line = line.expect("Failed to read stdin");
+        let update = match day1::parse_update::<T>(i, &line) {
+            Ok(Some(update)) => update,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("Failed to parse input: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        match online.apply(update) {
+            Ok(()) => println!(
+                "{}: Total: {} similarity_score: {}",
+                line.trim(),
+                online.distance(),
+                online.similarity()
+            ),
+            Err(e) => {
+                eprintln!("line {}: {}", i + 1, e);
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    exit_code
+}
+
+fn main() -> ExitCode {
+    aoc_common::logging::init(0);
+    let args = Args::parse();
+
+    if args.online {
+        return match args.ids {
+            Ids::U64 => run_online::<u64>(),
+            Ids::I64 => run_online::<i64>(),
+            Ids::Auto | Ids::I128 => run_online::<i128>(),
+        };
+    }
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .expect("Failed to read stdin");
 
-    println!("similarity_score: {}", similarity_score);
+    let id_type = match args.ids {
+        Ids::Auto => IdType::detect(input.split_whitespace()),
+        Ids::U64 => IdType::U64,
+        Ids::I64 => IdType::I64,
+        Ids::I128 => IdType::I128,
+    };
+    match id_type {
+        IdType::U64 => run::<u64>(&input, &args),
+        IdType::I64 => run::<i64>(&input, &args),
+        IdType::I128 => run::<i128>(&input, &args),
+    }
 }
@@ -0,0 +1,222 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use crate::{solve_part1, solve_part2, Id, Input, OverflowError};
+
+/// How a metric scored a pair of lists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Score<T> {
+    /// In the same units as the IDs
+    Id(T),
+    /// A count of positions or entries
+    Count(usize),
+    /// A fraction between 0 and 1
+    Ratio(f64),
+}
+
+impl<T: Display> Display for Score<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Score::Id(x) => write!(f, "{}", x),
+            Score::Count(x) => write!(f, "{}", x),
+            Score::Ratio(x) => write!(f, "{:.6}", x),
+        }
+    }
+}
+
+/// A way of measuring how well two sorted lists agree
+pub trait Metric<T: Id> {
+    /// Name to pick the metric by
+    fn name(&self) -> &'static str;
+
+    /// Label to print the score under
+    fn label(&self) -> &'static str {
+        self.name()
+    }
+
+    fn score(&self, input: &Input<T>) -> Result<Score<T>, OverflowError>;
+}
+
+/// Every built in metric. The first two are the puzzle's own scores
+pub fn metrics<T: Id>() -> Vec<Box<dyn Metric<T>>> {
+    vec![
+        Box::new(Distance),
+        Box::new(Similarity),
+        Box::new(SquaredDistance),
+        Box::new(MaxDistance),
+        Box::new(RankDistance),
+        Box::new(Overlap),
+    ]
+}
+
+/// Sum of the distances between sorted pairs, the puzzle's part 1
+pub struct Distance;
+
+impl<T: Id> Metric<T> for Distance {
+    fn name(&self) -> &'static str {
+        "distance"
+    }
+
+    fn label(&self) -> &'static str {
+        "Total"
+    }
+
+    fn score(&self, input: &Input<T>) -> Result<Score<T>, OverflowError> {
+        solve_part1(input).map(Score::Id)
+    }
+}
+
+/// Sum of each left ID times how often it's in the right list, the puzzle's part 2
+pub struct Similarity;
+
+impl<T: Id> Metric<T> for Similarity {
+    fn name(&self) -> &'static str {
+        "similarity"
+    }
+
+    fn label(&self) -> &'static str {
+        "similarity_score"
+    }
+
+    fn score(&self, input: &Input<T>) -> Result<Score<T>, OverflowError> {
+        solve_part2(input).map(Score::Id)
+    }
+}
+
+/// Sum of the squared distances between sorted pairs, which punishes big gaps more
+pub struct SquaredDistance;
+
+impl<T: Id> Metric<T> for SquaredDistance {
+    fn name(&self) -> &'static str {
+        "squared"
+    }
+
+    fn label(&self) -> &'static str {
+        "squared_distance"
+    }
+
+    fn score(&self, input: &Input<T>) -> Result<Score<T>, OverflowError> {
+        let overflow = || OverflowError::new::<T>("squared distance");
+
+        input
+            .list1
+            .iter()
+            .zip(&input.list2)
+            .try_fold(T::ZERO, |total, (&a, &b)| {
+                let distance = a.checked_distance(b).ok_or_else(overflow)?;
+                let squared = distance.checked_mul(distance).ok_or_else(overflow)?;
+                total.checked_add(squared).ok_or_else(overflow)
+            })
+            .map(Score::Id)
+    }
+}
+
+/// Biggest distance between any sorted pair
+pub struct MaxDistance;
+
+impl<T: Id> Metric<T> for MaxDistance {
+    fn name(&self) -> &'static str {
+        "max"
+    }
+
+    fn label(&self) -> &'static str {
+        "max_distance"
+    }
+
+    fn score(&self, input: &Input<T>) -> Result<Score<T>, OverflowError> {
+        input
+            .list1
+            .iter()
+            .zip(&input.list2)
+            .try_fold(T::ZERO, |max, (&a, &b)| {
+                let distance = a
+                    .checked_distance(b)
+                    .ok_or_else(|| OverflowError::new::<T>("max distance"))?;
+                Ok(max.max(distance))
+            })
+            .map(Score::Id)
+    }
+}
+
+/// How far each left ID's rank is from where it would rank in the right list, summed up. Unlike
+/// the distance this only cares about the order of the IDs, not how big they are
+pub struct RankDistance;
+
+impl<T: Id> Metric<T> for RankDistance {
+    fn name(&self) -> &'static str {
+        "rank"
+    }
+
+    fn label(&self) -> &'static str {
+        "rank_distance"
+    }
+
+    fn score(&self, input: &Input<T>) -> Result<Score<T>, OverflowError> {
+        let total = input
+            .list1
+            .iter()
+            .enumerate()
+            .map(|(rank, x)| rank.abs_diff(input.list2.partition_point(|y| y < x)))
+            .sum();
+
+        Ok(Score::Count(total))
+    }
+}
+
+/// Jaccard index of the lists as multisets: how many IDs they share, out of how many are in
+/// either, counting repeats
+pub struct Overlap;
+
+impl<T: Id> Metric<T> for Overlap {
+    fn name(&self) -> &'static str {
+        "overlap"
+    }
+
+    fn score(&self, input: &Input<T>) -> Result<Score<T>, OverflowError> {
+        // Both lists are sorted, so walk them together. Anything in both goes to the intersection
+        // and the union once, anything in only one goes to the union
+        let (a, b) = (&input.list1, &input.list2);
+        let (mut i, mut j, mut shared) = (0, 0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    shared += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        let either = a.len() + b.len() - shared;
+
+        Ok(Score::Ratio(if either == 0 {
+            1.0
+        } else {
+            shared as f64 / either as f64
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(metric: &dyn Metric<u64>) -> Score<u64> {
+        let input = crate::parse(include_str!("../example.txt")).unwrap();
+        metric.score(&input).unwrap()
+    }
+
+    #[test]
+    fn built_in_metrics() {
+        // Sorted pairs are (1, 3), (2, 3), (3, 3), (3, 4), (3, 5), (4, 9)
+        assert_eq!(score(&Distance), Score::Id(11));
+        assert_eq!(score(&Similarity), Score::Id(31));
+        // 4 + 1 + 0 + 1 + 4 + 25
+        assert_eq!(score(&SquaredDistance), Score::Id(35));
+        assert_eq!(score(&MaxDistance), Score::Id(5));
+        // Left ranks 0..6 land at right ranks 0, 0, 0, 0, 0, 3
+        assert_eq!(score(&RankDistance), Score::Count(1 + 2 + 3 + 4 + 2));
+        // Shared are 3, 3, 3 and 4, out of 8 in either
+        assert_eq!(score(&Overlap), Score::Ratio(0.5));
+    }
+}
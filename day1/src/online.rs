@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::{Id, OverflowError};
+
+/// Most IDs a chunk holds before it gets split in two
+const CHUNK_SIZE: usize = 512;
+
+/// A sorted multiset which can find the rank of an ID and walk from any rank. It's kept as a list
+/// of sorted chunks, so inserting or removing only shifts the IDs in one chunk
+#[derive(Debug, Clone)]
+pub struct OrderedMultiset<T> {
+    chunks: Vec<Vec<T>>,
+    len: usize,
+}
+
+impl<T: Ord + Copy> Default for OrderedMultiset<T> {
+    fn default() -> Self {
+        Self {
+            chunks: vec![],
+            len: 0,
+        }
+    }
+}
+
+impl<T: Ord + Copy> OrderedMultiset<T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Index of the first chunk which could hold `x`, along with how many IDs come before it
+    fn chunk_for(&self, x: T) -> (usize, usize) {
+        let mut before = 0;
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if chunk.last().is_some_and(|&last| last >= x) {
+                return (i, before);
+            }
+            before += chunk.len();
+        }
+
+        let last = self.chunks.len().saturating_sub(1);
+        (last, before - self.chunks.get(last).map_or(0, Vec::len))
+    }
+
+    /// Adds an ID, returning the rank it ended up at
+    pub fn insert(&mut self, x: T) -> usize {
+        if self.chunks.is_empty() {
+            self.chunks.push(vec![]);
+        }
+        let (i, before) = self.chunk_for(x);
+        let chunk = &mut self.chunks[i];
+        let j = chunk.partition_point(|&y| y < x);
+        chunk.insert(j, x);
+        self.len += 1;
+
+        if chunk.len() > CHUNK_SIZE * 2 {
+            let tail = chunk.split_off(CHUNK_SIZE);
+            self.chunks.insert(i + 1, tail);
+        }
+
+        before + j
+    }
+
+    /// The rank of the first copy of an ID, if there is one
+    pub fn rank(&self, x: T) -> Option<usize> {
+        let (i, before) = self.chunk_for(x);
+        let chunk = self.chunks.get(i)?;
+        let j = chunk.partition_point(|&y| y < x);
+
+        (chunk.get(j) == Some(&x)).then_some(before + j)
+    }
+
+    /// Takes out one copy of an ID, returning the rank it was at
+    pub fn remove(&mut self, x: T) -> Option<usize> {
+        let rank = self.rank(x)?;
+        let (i, before) = self.chunk_for(x);
+        self.chunks[i].remove(rank - before);
+        if self.chunks[i].is_empty() {
+            self.chunks.remove(i);
+        }
+        self.len -= 1;
+
+        Some(rank)
+    }
+
+    /// Walks the IDs in order, starting at a rank
+    pub fn iter_from(&self, rank: usize) -> impl Iterator<Item = T> + '_ {
+        // Skip over whole chunks first
+        let mut skip = rank;
+        let mut first = self.chunks.len();
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if skip < chunk.len() {
+                first = i;
+                break;
+            }
+            skip -= chunk.len();
+        }
+
+        self.chunks[first..].iter().flatten().skip(skip).copied()
+    }
+}
+
+/// A change to the lists: a pair of IDs going in or coming out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update<T> {
+    Insert(T, T),
+    Remove(T, T),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum UpdateError {
+    #[error("the {list} list doesn't contain {id}")]
+    Missing { list: &'static str, id: String },
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+}
+
+/// Keeps the total distance and similarity score up to date as pairs of IDs come and go, without
+/// re-sorting anything
+#[derive(Debug, Clone)]
+pub struct Online<T> {
+    left: OrderedMultiset<T>,
+    right: OrderedMultiset<T>,
+    left_counts: HashMap<T, usize>,
+    right_counts: HashMap<T, usize>,
+    distance: T,
+    similarity: T,
+}
+
+impl<T: Id> Default for Online<T> {
+    fn default() -> Self {
+        Self {
+            left: OrderedMultiset::default(),
+            right: OrderedMultiset::default(),
+            left_counts: HashMap::new(),
+            right_counts: HashMap::new(),
+            distance: T::ZERO,
+            similarity: T::ZERO,
+        }
+    }
+}
+
+impl<T: Id> Online<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.left.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty()
+    }
+
+    /// The puzzle's part 1 score for the lists so far
+    pub fn distance(&self) -> T {
+        self.distance
+    }
+
+    /// The puzzle's part 2 score for the lists so far
+    pub fn similarity(&self) -> T {
+        self.similarity
+    }
+
+    /// Sums up the distances of the sorted pairs which move when the IDs at rank `p` of the left
+    /// list and rank `q` of the right list come or go. Everything before both ranks keeps its
+    /// partner, and everything after both just shifts along one, so only the pairs in between
+    /// matter. Gives the sums with and without those two IDs
+    fn window(&self, p: usize, q: usize) -> Result<(T, T), OverflowError> {
+        let (lo, hi) = (p.min(q), p.max(q));
+        let left = self
+            .left
+            .iter_from(lo)
+            .take(hi - lo + 1)
+            .collect::<Vec<_>>();
+        let right = self
+            .right
+            .iter_from(lo)
+            .take(hi - lo + 1)
+            .collect::<Vec<_>>();
+
+        let sum = |left: &mut dyn Iterator<Item = T>, right: &mut dyn Iterator<Item = T>| {
+            let overflow = || OverflowError::new::<T>("total distance");
+            left.zip(right).try_fold(T::ZERO, |total, (a, b)| {
+                total
+                    .checked_add(a.checked_distance(b).ok_or_else(overflow)?)
+                    .ok_or_else(overflow)
+            })
+        };
+        let with = sum(&mut left.iter().copied(), &mut right.iter().copied())?;
+        let without = sum(
+            &mut left
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != p - lo)
+                .map(|(_, &a)| a),
+            &mut right
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != q - lo)
+                .map(|(_, &b)| b),
+        )?;
+
+        Ok((with, without))
+    }
+
+    /// How much a pair adds to the similarity score, while it's in the lists. `a` matches every
+    /// copy of itself on the right, and `b` every copy of itself on the left, but if they're the
+    /// same ID that would count them matching each other twice
+    fn pair_similarity(&self, a: T, b: T) -> Result<T, OverflowError> {
+        let count = |counts: &HashMap<T, usize>, x| counts.get(&x).copied().unwrap_or(0);
+
+        a.checked_mul_count(count(&self.right_counts, a) - usize::from(a == b))
+            .and_then(|x| x.checked_add(b.checked_mul_count(count(&self.left_counts, b))?))
+            .ok_or_else(|| OverflowError::new::<T>("similarity score"))
+    }
+
+    /// Works out both scores with the pair at ranks `p` and `q` in or out of the lists. Has to be
+    /// called while the pair is in them
+    fn scores(
+        &self,
+        a: T,
+        b: T,
+        p: usize,
+        q: usize,
+        inserting: bool,
+    ) -> Result<(T, T), OverflowError> {
+        let (with, without) = self.window(p, q)?;
+        let pair_similarity = self.pair_similarity(a, b)?;
+
+        let (distance, similarity) = if inserting {
+            (
+                self.distance
+                    .checked_add(with)
+                    .and_then(|d| d.checked_sub(without)),
+                self.similarity.checked_add(pair_similarity),
+            )
+        } else {
+            (
+                self.distance
+                    .checked_sub(with)
+                    .and_then(|d| d.checked_add(without)),
+                self.similarity.checked_sub(pair_similarity),
+            )
+        };
+
+        Ok((
+            distance.ok_or_else(|| OverflowError::new::<T>("total distance"))?,
+            similarity.ok_or_else(|| OverflowError::new::<T>("similarity score"))?,
+        ))
+    }
+
+    /// Applies an update, leaving everything as it was if it fails
+    pub fn apply(&mut self, update: Update<T>) -> Result<(), UpdateError> {
+        let (distance, similarity) = match update {
+            Update::Insert(a, b) => {
+                let p = self.left.insert(a);
+                let q = self.right.insert(b);
+                *self.left_counts.entry(a).or_default() += 1;
+                *self.right_counts.entry(b).or_default() += 1;
+
+                self.scores(a, b, p, q, true)
+                    .inspect_err(|_| self.take_out(a, b))?
+            }
+            Update::Remove(a, b) => {
+                let missing = |list, id: T| UpdateError::Missing {
+                    list,
+                    id: id.to_string(),
+                };
+                let p = self.left.rank(a).ok_or_else(|| missing("left", a))?;
+                let q = self.right.rank(b).ok_or_else(|| missing("right", b))?;
+
+                let scores = self.scores(a, b, p, q, false)?;
+                self.take_out(a, b);
+                scores
+            }
+        };
+
+        self.distance = distance;
+        self.similarity = similarity;
+
+        Ok(())
+    }
+
+    /// Takes a pair out of the lists, without touching the scores
+    fn take_out(&mut self, a: T, b: T) {
+        self.left.remove(a);
+        self.right.remove(b);
+        for (counts, x) in [(&mut self.left_counts, a), (&mut self.right_counts, b)] {
+            let count = counts.get_mut(&x).expect("Pair is in the lists");
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&x);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, solve_part1, solve_part2, Input};
+
+    /// Checks the running scores against scoring the whole lists from scratch
+    fn check(online: &Online<i64>, pairs: &[(i64, i64)]) {
+        let mut input = Input {
+            list1: pairs.iter().map(|&(a, _)| a).collect(),
+            list2: pairs.iter().map(|&(_, b)| b).collect(),
+        };
+        input.list1.sort();
+        input.list2.sort();
+
+        assert_eq!(online.distance(), solve_part1(&input).unwrap());
+        assert_eq!(online.similarity(), solve_part2(&input).unwrap());
+    }
+
+    #[test]
+    fn streamed_example() {
+        let example = parse(include_str!("../example.txt")).unwrap();
+        let lines = include_str!("../example.txt").lines().enumerate();
+
+        let mut online = Online::<u64>::new();
+        for (i, line) in lines {
+            online
+                .apply(crate::parse_update(i, line).unwrap().unwrap())
+                .unwrap();
+        }
+        assert_eq!(online.distance(), solve_part1(&example).unwrap());
+        assert_eq!(online.similarity(), solve_part2(&example).unwrap());
+    }
+
+    #[test]
+    fn inserts_and_removes_match_batch() {
+        // Small IDs so there's plenty of repeats, and enough of them to split chunks
+        let mut seed = 42_u64;
+        let mut random = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as i64 % 50 - 10
+        };
+
+        let mut online = Online::new();
+        let mut pairs = vec![];
+        for step in 0..6000 {
+            if step % 3 == 2 {
+                let (a, b) = pairs.swap_remove(random().unsigned_abs() as usize % pairs.len());
+                online.apply(Update::Remove(a, b)).unwrap();
+            } else {
+                let pair = (random(), random());
+                online.apply(Update::Insert(pair.0, pair.1)).unwrap();
+                pairs.push(pair);
+            }
+
+            if step % 97 == 0 {
+                check(&online, &pairs);
+            }
+        }
+        check(&online, &pairs);
+    }
+
+    #[test]
+    fn failed_updates_change_nothing() {
+        let mut online = Online::<u64>::new();
+        online.apply(Update::Insert(1, u64::MAX)).unwrap();
+
+        assert!(matches!(
+            online.apply(Update::Remove(2, 2)),
+            Err(UpdateError::Missing { list: "left", .. })
+        ));
+        assert!(matches!(
+            online.apply(Update::Insert(0, u64::MAX)),
+            Err(UpdateError::Overflow(_))
+        ));
+        assert_eq!(online.len(), 1);
+        assert_eq!(online.distance(), u64::MAX - 1);
+    }
+}
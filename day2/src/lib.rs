@@ -0,0 +1,506 @@
+use std::fmt;
+
+use aoc_common::Location;
+use thiserror::Error;
+use tracing::trace;
+
+mod monitor;
+mod policy;
+mod stream;
+
+pub use monitor::{levels, Alert, Levels, Monitor, MonitorError};
+pub use policy::{Direction, PolicyError, Rule, SafetyPolicy, ShortReports};
+pub use stream::{write_csv, StreamError};
+
+/// Whether a report is safe, and why not if it isn't
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Safety {
+    Safe,
+    Unsafe(Violation),
+}
+
+impl Safety {
+    pub fn is_safe(&self) -> bool {
+        *self == Safety::Safe
+    }
+}
+
+/// The first bad step in a report: the rule it breaks and the indices of the levels either side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub rule: Rule,
+    pub first: usize,
+    pub second: usize,
+}
+
+/// Counts levels from 1, the way they're shown to people
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Rule::TooShort { .. } = self.rule {
+            return write!(f, "{}", self.rule);
+        }
+        write!(
+            f,
+            "{} between levels {} and {}",
+            self.rule,
+            self.first + 1,
+            self.second + 1
+        )
+    }
+}
+
+/// A list of levels
+pub type Report = Vec<u64>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("{location}: expected a level, found {found:?}")]
+    InvalidLevel { location: Location, found: String },
+}
+
+/// Parses one report per line, with levels separated by any whitespace. Blank lines aren't
+/// reports, so they're skipped, but still count towards the line numbers in errors
+pub fn parse(input: &str) -> Result<Vec<Report>, ParseError> {
+    parse_numbered(input).map(|reports| reports.into_iter().map(|(_, report)| report).collect())
+}
+
+/// Parses the reports like [`parse`], along with the index of the line each one is on, so they can
+/// be pointed at the same way as errors and CSV rows are
+pub fn parse_numbered(input: &str) -> Result<Vec<(usize, Report)>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !is_blank(l))
+        .map(|(i, l)| Ok((i, parse_line(i, l)?)))
+        .collect()
+}
+
+/// Whether a line has nothing but whitespace on it
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Parses the report on line `i`
+fn parse_line(i: usize, line: &str) -> Result<Report, ParseError> {
+    line.split_whitespace()
+        .map(|x| {
+            x.parse::<u64>().map_err(|_| ParseError::InvalidLevel {
+                location: Location::of_token(i, line, x),
+                found: x.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Finds the first pair of neighbouring levels with a bad step between them, pretending the level
+/// at `skip` isn't there
+fn first_bad_step(
+    report: &[u64],
+    policy: &SafetyPolicy,
+    skip: Option<usize>,
+    increasing: bool,
+) -> Option<Violation> {
+    let mut levels = report
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|&(i, _)| Some(i) != skip);
+
+    let mut previous = levels.next()?;
+    for level in levels {
+        if let Err(rule) = policy.check_step(previous.1, level.1, increasing) {
+            return Some(Violation {
+                rule,
+                first: previous.0,
+                second: level.0,
+            });
+        }
+        previous = level;
+    }
+
+    None
+}
+
+/// Checks if a report is safe. When it could go either way, the violation given is from whichever
+/// way the report keeps up for longest
+pub fn check_report(report: &[u64], policy: &SafetyPolicy) -> Safety {
+    if policy.is_too_short(report.len()) {
+        return Safety::Unsafe(Violation {
+            rule: Rule::TooShort {
+                levels: report.len(),
+            },
+            first: 0,
+            second: report.len().saturating_sub(1),
+        });
+    }
+
+    let mut worst: Option<Violation> = None;
+    for &increasing in policy.directions() {
+        match first_bad_step(report, policy, None, increasing) {
+            None => return Safety::Safe,
+            Some(v) if worst.is_none_or(|w| v.second > w.second) => worst = Some(v),
+            Some(_) => {}
+        }
+    }
+
+    Safety::Unsafe(worst.expect("a policy always allows some direction"))
+}
+
+/// Checks if a report is safe under the given rules
+pub fn is_safe(report: &[u64], policy: &SafetyPolicy) -> bool {
+    check_report(report, policy).is_safe()
+}
+
+pub fn solve_part1(input: &[Report]) -> usize {
+    let policy = SafetyPolicy::default();
+
+    input
+        .iter()
+        // Check if the report is safe
+        .map(|r| check_report(r, &policy))
+        // Count them
+        .filter(Safety::is_safe)
+        .count()
+}
+
+/// Checks a report with each of the levels removed one by one
+fn dampen_dumb(report: &[u64], policy: &SafetyPolicy) -> Safety {
+    (0..report.len())
+        .map(|i| {
+            report[..i]
+                .iter()
+                .chain(report[i + 1..].iter())
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .map(|r| check_report(&r, policy))
+        // Check if any of the modified reports are safe
+        .find(Safety::is_safe)
+        .unwrap_or_else(|| check_report(report, policy))
+}
+
+/// Removes each of the levels one by one and checks the report again
+pub fn solve_part2(input: &[Report]) -> usize {
+    let policy = SafetyPolicy::default();
+
+    input
+        .iter()
+        // Remove each of the levels one by one and measure the safety
+        .map(|r| dampen_dumb(r, &policy))
+        // Count them
+        .filter(Safety::is_safe)
+        .count()
+}
+
+/// What the Problem Dampener makes of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dampened {
+    Safe,
+    /// Safe once the level at this index is removed
+    SafeWithout(usize),
+    /// Unsafe whichever level is removed, with what's wrong with the whole report
+    Unsafe(Violation),
+}
+
+impl Dampened {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Dampened::Unsafe(_))
+    }
+}
+
+/// Checks if a report is safe with at most one level removed, in linear time
+pub fn dampen(report: &[u64], policy: &SafetyPolicy) -> Dampened {
+    // Removing a level would leave the report too short, so it has to be safe as it is
+    if policy.is_too_short(report.len().saturating_sub(1)) {
+        return match check_report(report, policy) {
+            Safety::Safe => Dampened::Safe,
+            Safety::Unsafe(violation) => Dampened::Unsafe(violation),
+        };
+    }
+
+    for &increasing in policy.directions() {
+        // Removing any level away from the first bad step leaves that step in place, so the only
+        // levels worth trying to remove are the two either side of it
+        let Some(violation) = first_bad_step(report, policy, None, increasing) else {
+            return Dampened::Safe;
+        };
+        for removed in [violation.first, violation.second] {
+            if first_bad_step(report, policy, Some(removed), increasing).is_none() {
+                return Dampened::SafeWithout(removed);
+            }
+        }
+    }
+
+    match check_report(report, policy) {
+        Safety::Unsafe(violation) => Dampened::Unsafe(violation),
+        Safety::Safe => unreachable!("safe reports have no bad step"),
+    }
+}
+
+/// Linear-time version of `solve_part2`, which only ever tries removing the levels either side of
+/// the first bad step
+pub fn solve_part2_smort(input: &[Report]) -> usize {
+    let policy = SafetyPolicy::default();
+
+    input
+        .iter()
+        .map(|r| {
+            let dampened = dampen(r, &policy);
+            trace!(report = ?r, ?dampened);
+            dampened
+        })
+        .filter(Dampened::is_safe)
+        .count()
+}
+
+/// The fewest levels which have to be removed to make a report safe. The kept levels have to form
+/// a safe report on their own, so this finds the longest safe subsequence with dynamic
+/// programming: the longest one ending at each level is one more than the longest ending at any
+/// earlier level it can safely follow. Gives `None` if short reports are unsafe and no two levels
+/// can be kept
+pub fn min_removals(report: &[u64], policy: &SafetyPolicy) -> Option<usize> {
+    let longest = policy
+        .directions()
+        .iter()
+        .map(|&increasing| {
+            let mut ending_at = Vec::with_capacity(report.len());
+            for (i, &level) in report.iter().enumerate() {
+                let longest_before = (0..i)
+                    .filter(|&j| policy.is_valid_step(report[j], level, increasing))
+                    .map(|j| ending_at[j])
+                    .max()
+                    .unwrap_or(0);
+                ending_at.push(longest_before + 1);
+            }
+            ending_at.into_iter().max().unwrap_or(0)
+        })
+        .max()
+        .unwrap_or(0);
+
+    (!policy.is_too_short(longest)).then(|| report.len() - longest)
+}
+
+/// Counts the reports which are safe with at most `max_removals` levels removed. The puzzle's own
+/// cases of none or one removal get the linear checks
+pub fn count_safe_within(input: &[Report], policy: &SafetyPolicy, max_removals: usize) -> usize {
+    input
+        .iter()
+        .filter(|r| match max_removals {
+            0 => is_safe(r, policy),
+            1 => dampen(r, policy).is_safe(),
+            k => min_removals(r, policy).is_some_and(|n| n <= k),
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    fn policy() -> impl Strategy<Value = SafetyPolicy> {
+        (
+            0_u64..3,
+            0_u64..4,
+            any::<bool>(),
+            prop_oneof![
+                Just(Direction::Increasing),
+                Just(Direction::Decreasing),
+                Just(Direction::Either)
+            ],
+            prop_oneof![Just(ShortReports::Safe), Just(ShortReports::Unsafe)],
+        )
+            .prop_map(
+                |(min_step, extra, allow_plateaus, direction, short_reports)| SafetyPolicy {
+                    min_step,
+                    max_step: min_step + extra,
+                    allow_plateaus,
+                    direction,
+                    short_reports,
+                },
+            )
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(&parse(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(&parse(EXAMPLE).unwrap()), 4);
+        assert_eq!(solve_part2_smort(&parse(EXAMPLE).unwrap()), 4);
+    }
+
+    #[test]
+    fn example_min_removals() {
+        let removals = parse(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|r| min_removals(r, &SafetyPolicy::default()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            removals,
+            [Some(0), Some(2), Some(2), Some(1), Some(1), Some(0)]
+        );
+        assert_eq!(
+            count_safe_within(&parse(EXAMPLE).unwrap(), &SafetyPolicy::default(), 2),
+            6
+        );
+    }
+
+    #[test]
+    fn example_reasons() {
+        let policy = SafetyPolicy::default();
+        let violation = |rule, first| {
+            Safety::Unsafe(Violation {
+                rule,
+                first,
+                second: first + 1,
+            })
+        };
+        let safety = parse(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|r| check_report(r, &policy))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            safety,
+            [
+                Safety::Safe,
+                violation(Rule::StepTooLarge { step: 5 }, 1),
+                violation(Rule::StepTooLarge { step: 4 }, 2),
+                violation(Rule::DirectionChange, 1),
+                violation(Rule::Plateau, 2),
+                Safety::Safe,
+            ]
+        );
+
+        let dampened = parse(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|r| dampen(r, &policy))
+            .collect::<Vec<_>>();
+        assert_eq!(dampened[3], Dampened::SafeWithout(1));
+        assert_eq!(dampened[4], Dampened::SafeWithout(2));
+        assert!(!dampened[1].is_safe());
+    }
+
+    #[test]
+    fn parse_blank_lines() {
+        assert_eq!(
+            parse("\n1 2  3\n\n \t\n4\t5\r\n6\n\n\n").unwrap(),
+            [vec![1, 2, 3], vec![4, 5], vec![6]]
+        );
+        assert_eq!(parse("").unwrap(), Vec::<Report>::new());
+        assert_eq!(parse("\n \n").unwrap(), Vec::<Report>::new());
+
+        // Stray blank lines mustn't turn into safe empty reports
+        assert_eq!(solve_part1(&parse("\n\n1 9\n\n").unwrap()), 0);
+        assert_eq!(
+            parse_numbered("\n1 2\n\n3\n").unwrap(),
+            [(1, vec![1, 2]), (3, vec![3])]
+        );
+
+        // Skipped lines still count towards where errors are
+        assert_eq!(
+            parse("1 2\n\n  \n3 x").unwrap_err(),
+            ParseError::InvalidLevel {
+                location: Location::new(3, 2),
+                found: "x".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn short_reports() {
+        let safe = SafetyPolicy::default();
+        let unsafe_ = SafetyPolicy {
+            short_reports: ShortReports::Unsafe,
+            ..safe.clone()
+        };
+
+        for report in [&[][..], &[5]] {
+            assert!(is_safe(report, &safe));
+            assert!(dampen(report, &safe).is_safe());
+            assert_eq!(min_removals(report, &safe), Some(0));
+
+            assert_eq!(
+                check_report(report, &unsafe_),
+                Safety::Unsafe(Violation {
+                    rule: Rule::TooShort {
+                        levels: report.len()
+                    },
+                    first: 0,
+                    second: 0,
+                })
+            );
+            assert!(!dampen(report, &unsafe_).is_safe());
+            assert_eq!(min_removals(report, &unsafe_), None);
+        }
+
+        // Dampening a bad pair would leave a single level
+        assert_eq!(dampen(&[1, 9], &safe), Dampened::SafeWithout(0));
+        assert!(!dampen(&[1, 9], &unsafe_).is_safe());
+        assert_eq!(min_removals(&[1, 9], &unsafe_), None);
+        assert_eq!(min_removals(&[1, 9, 2], &unsafe_), Some(1));
+        assert_eq!(
+            count_safe_within(&[vec![], vec![1], vec![1, 2], vec![1, 9]], &unsafe_, 5),
+            1
+        );
+    }
+
+    proptest! {
+        /// No removals and one removal have to agree with checking directly and the brute force,
+        /// under any policy
+        #[test]
+        fn min_removals_matches_dumb(report in vec(0_u64..12, 0..10), policy in policy()) {
+            let removals = min_removals(&report, &policy);
+            prop_assert_eq!(removals == Some(0), is_safe(&report, &policy));
+            prop_assert_eq!(
+                removals.is_some_and(|n| n <= 1),
+                dampen_dumb(&report, &policy).is_safe()
+            );
+        }
+
+        /// The dampener has to agree with the brute force under any policy too
+        #[test]
+        fn dampen_matches_dumb(report in vec(0_u64..12, 0..10), policy in policy()) {
+            prop_assert_eq!(
+                dampen(&report, &policy).is_safe(),
+                dampen_dumb(&report, &policy).is_safe()
+            );
+        }
+
+        /// The dampener has to agree with trying every removal, report by report. Levels are kept
+        /// close together so plenty of the reports are nearly safe
+        #[test]
+        fn smort_matches_dumb(report in vec(0_u64..12, 3..10)) {
+            let reports = [report];
+            prop_assert_eq!(solve_part2_smort(&reports), solve_part2(&reports));
+        }
+
+        /// Nearly safe reports are rare by chance, so also try safe ones with a level knocked out
+        #[test]
+        fn smort_matches_dumb_nearly_safe(
+            start in 30_u64..40,
+            steps in vec(1_u64..=3, 3..10),
+            increasing: bool,
+            bad_index: prop::sample::Index,
+            bad_level in 0_u64..40,
+        ) {
+            let mut report = steps.iter().scan(start, |level, step| {
+                *level = if increasing { *level + step } else { *level - step };
+                Some(*level)
+            }).collect::<Vec<_>>();
+            let bad_index = bad_index.index(report.len());
+            report[bad_index] = bad_level;
+
+            let reports = [report];
+            prop_assert_eq!(solve_part2_smort(&reports), solve_part2(&reports));
+        }
+    }
+}
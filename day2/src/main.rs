@@ -1,140 +1,195 @@
-#[derive(Debug, PartialEq, Eq)]
-enum Safety {
+use std::{fs, io::Read, path::PathBuf, process::ExitCode};
+
+use clap::{Parser, ValueEnum};
+use day2::{Dampened, Direction, Monitor, Safety, SafetyPolicy, ShortReports};
+
+#[derive(Parser)]
+#[command(about = "Counts the safe reports read from stdin")]
+struct Args {
+    /// Most levels which can be removed from a report to make it safe
+    #[arg(short = 'k', long, default_value_t = 1)]
+    max_removals: usize,
+    /// Also print the fewest levels each report needs removed to be safe
+    #[arg(long)]
+    show_removals: bool,
+    /// List every unsafe report with what's wrong with it, and which removed level makes it safe
+    #[arg(long)]
+    report: bool,
+    /// Stream a CSV row for every report instead, checking them in parallel without reading the
+    /// whole input in first. Only one removal is tried, with `-k 0` turning that off
+    #[arg(long, conflicts_with_all = ["show_removals", "report"])]
+    csv: bool,
+    /// Treat stdin as one endless stream of levels instead, checking the last WINDOW of them as
+    /// each one comes in and alerting when they become unsafe. Only one removal is tried, with
+    /// `-k 0` turning that off
+    #[arg(long, value_name = "WINDOW",
+          value_parser = clap::value_parser!(u64).range(2..),
+          conflicts_with_all = ["show_removals", "report", "csv"])]
+    monitor: Option<u64>,
+    /// TOML file with the safety policy, anything it leaves out keeps the puzzle's rules
+    #[arg(long)]
+    policy: Option<PathBuf>,
+    /// Smallest change allowed between neighbouring levels, overriding the policy file
+    #[arg(long)]
+    min_step: Option<u64>,
+    /// Biggest change allowed between neighbouring levels, overriding the policy file
+    #[arg(long)]
+    max_step: Option<u64>,
+    /// Whether neighbouring levels can be the same, overriding the policy file
+    #[arg(long, value_name = "BOOL")]
+    allow_plateaus: Option<bool>,
+    /// Which way reports have to go, overriding the policy file
+    #[arg(long, value_enum)]
+    direction: Option<DirectionArg>,
+    /// Whether empty and single-level reports are safe, overriding the policy file
+    #[arg(long, value_enum)]
+    short_reports: Option<ShortReportsArg>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DirectionArg {
+    Increasing,
+    Decreasing,
+    Either,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ShortReportsArg {
     Safe,
     Unsafe,
 }
 
-fn part1() {
-    let num_safe = std::io::stdin()
-        // Parse inputs
-        .lines()
-        .map(Result::unwrap)
-        .map(|l| {
-            l.split(" ")
-                .map(|x| u64::from_str_radix(x, 10).unwrap())
-                .collect::<Vec<_>>()
-        })
-        // Check if the report is safe
-        .map(|r| check_report(&r))
-        // Count them
-        .filter(|s| *s == Safety::Safe)
-        .count();
-    println!("Safe reports: {}", num_safe);
+/// Builds the policy from the file if there is one, then the flags on top
+fn policy(args: &Args) -> Result<SafetyPolicy, String> {
+    let mut policy = match &args.policy {
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            SafetyPolicy::from_toml(&text).map_err(|e| e.to_string())?
+        }
+        None => SafetyPolicy::default(),
+    };
+
+    if let Some(min_step) = args.min_step {
+        policy.min_step = min_step;
+    }
+    if let Some(max_step) = args.max_step {
+        policy.max_step = max_step;
+    }
+    if let Some(allow_plateaus) = args.allow_plateaus {
+        policy.allow_plateaus = allow_plateaus;
+    }
+    if let Some(direction) = args.direction {
+        policy.direction = match direction {
+            DirectionArg::Increasing => Direction::Increasing,
+            DirectionArg::Decreasing => Direction::Decreasing,
+            DirectionArg::Either => Direction::Either,
+        };
+    }
+
+    if let Some(short_reports) = args.short_reports {
+        policy.short_reports = match short_reports {
+            ShortReportsArg::Safe => ShortReports::Safe,
+            ShortReportsArg::Unsafe => ShortReports::Unsafe,
+        };
+    }
+
+    policy.validate().map_err(|e| e.to_string())?;
+    Ok(policy)
 }
 
-/// Checks if a report is safe
-fn check_report(report: &[u64]) -> Safety {
-    let max_diff = report
-        .windows(2)
-        .map(|w| w[0].abs_diff(w[1]))
-        .max()
-        .unwrap();
-    let ordering = report
-        .windows(2)
-        .map(|w| w[0].cmp(&w[1]))
-        .collect::<Vec<_>>();
-
-    if max_diff > 3 {
-        Safety::Unsafe
-    } else if ordering.windows(2).any(|w| w[0] != w[1]) {
-        Safety::Unsafe
-    } else {
-        match ordering[0] {
-            std::cmp::Ordering::Equal => Safety::Unsafe,
-            _ => Safety::Safe,
+fn main() -> ExitCode {
+    aoc_common::logging::init(0);
+    let args = Args::parse();
+
+    let policy = match policy(&args) {
+        Ok(policy) => policy,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
         }
+    };
+
+    if (args.csv || args.monitor.is_some()) && args.max_removals > 1 {
+        eprintln!("--csv and --monitor try at most one removal");
+        return ExitCode::FAILURE;
     }
-}
 
-fn part2_dumb() {
-    let num_safe = std::io::stdin()
-        // Parse inputs
-        .lines()
-        .map(Result::unwrap)
-        .map(|l| {
-            l.split(" ")
-                .map(|x| u64::from_str_radix(x, 10).unwrap())
-                .collect::<Vec<_>>()
-        })
-        // Remove each of the levels one by one and measure the safety
-        .map(|r| {
-            (0..r.len())
-                .map(|i| {
-                    r[..i]
-                        .iter()
-                        .chain(r[i + 1..].iter())
-                        .cloned()
-                        .collect::<Vec<_>>()
-                })
-                .map(|r| check_report(&r))
-                .collect::<Vec<_>>()
-        })
-        // Check if any of the modified reports are safe
-        .map(|reports| {
-            reports
-                .into_iter()
-                .find(|s| *s == Safety::Safe)
-                .unwrap_or(Safety::Unsafe)
-        })
-        // Count them
-        .filter(|s| *s == Safety::Safe)
-        .count();
-    println!("Safe reports: {}", num_safe);
-}
+    if let Some(window) = args.monitor {
+        let mut monitor = Monitor::new(policy, window, args.max_removals == 1);
+        for level in day2::levels(std::io::stdin().lock()) {
+            match level {
+                Ok(level) => {
+                    if let Some(alert) = monitor.push(level) {
+                        println!("{}", alert);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Failed to read levels: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    if args.csv {
+        let stdin = std::io::stdin().lock();
+        let stdout = std::io::BufWriter::new(std::io::stdout().lock());
+        return match day2::write_csv(stdin, stdout, &policy, args.max_removals == 1) {
+            Ok(_) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("Failed to write CSV: {}", e);
+                ExitCode::FAILURE
+            }
+        };
+    }
 
-fn part2_smort() {
-    let num_safe = std::io::stdin()
-        // Parse inputs
-        .lines()
-        .map(Result::unwrap)
-        .map(|l| {
-            l.split(" ")
-                .map(|x| u64::from_str_radix(x, 10).unwrap())
-                .collect::<Vec<_>>()
-        })
-        // Check if the report is safe
-        .map(|mut r| {
-            println!("{:?}", r);
-
-            // Identify the first bad level
-            let differences = r
-                .windows(2)
-                .map(|w| w[1] as i64 - w[0] as i64)
-                .collect::<Vec<_>>();
-            println!("\tdiffs: {:?}", differences);
-
-            let signs = differences.iter().map(|d| d.cmp(&0)).collect::<Vec<_>>();
-            println!("\tsigns: {:?}", signs);
-
-            let first_bad = differences
-                .iter()
-                .zip(&signs)
-                .enumerate()
-                .find(|&(_, (d, s))| {
-                    *s == std::cmp::Ordering::Equal || *s != signs[0] || d.abs() > 3
-                });
-            println!("\tfirst_bad: {:?}", first_bad);
-
-            // remove it
-            // todo: we need to decide whether to remove this one, or the one before it
-            if let Some((i, _)) = first_bad {
-                let mut r1 = r.clone();
-                r1.remove(i);
-                println!("\trem first: {:?}", check_report(&r1));
-
-                let mut r2 = r.clone();
-                r2.remove(i + 1);
-                println!("\trem 2nd: {:?}", check_report(&r2));
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .expect("Failed to read stdin");
+    // Reports are numbered by the line they're on, the same as in errors and CSV rows
+    let (lines, input): (Vec<_>, Vec<_>) = match day2::parse_numbered(&input) {
+        Ok(input) => input.into_iter().unzip(),
+        Err(e) => {
+            eprintln!("Failed to parse input: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.show_removals {
+        for (i, report) in lines.iter().zip(&input) {
+            match day2::min_removals(report, &policy) {
+                Some(1) => println!("Report {}: 1 removal", i + 1),
+                Some(removals) => println!("Report {}: {} removals", i + 1, removals),
+                None => println!("Report {}: can't be made safe", i + 1),
+            }
+        }
+    }
+
+    if args.report {
+        for (i, report) in lines.iter().zip(&input) {
+            let Safety::Unsafe(violation) = day2::check_report(report, &policy) else {
+                continue;
             };
+            match day2::dampen(report, &policy) {
+                Dampened::SafeWithout(removed) if args.max_removals > 0 => println!(
+                    "Report {}: unsafe, {}; safe without level {} ({})",
+                    i + 1,
+                    violation,
+                    removed + 1,
+                    report[removed]
+                ),
+                _ => println!("Report {}: unsafe, {}", i + 1, violation),
+            }
+        }
+    }
 
-            check_report(&r)
-        })
-        // Count them
-        .filter(|s| *s == Safety::Safe)
-        .count();
-    println!("Safe reports: {}", num_safe);
-}
+    println!(
+        "Safe reports: {}",
+        day2::count_safe_within(&input, &policy, args.max_removals)
+    );
 
-fn main() {
-    part2_dumb();
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,463 @@
+use std::{
+    fmt,
+    io::{self, BufRead},
+};
+
+use aoc_common::Location;
+use thiserror::Error;
+
+use crate::{ParseError, Rule, SafetyPolicy};
+
+#[derive(Debug, Error)]
+pub enum MonitorError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// A span of the stream which has just become unsafe. Offsets count levels from the start of the
+/// stream, from 0, and `end` is included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alert {
+    pub start: u64,
+    pub end: u64,
+    pub rule: Rule,
+    /// Offsets of the levels either side of the bad step
+    pub first: u64,
+    pub second: u64,
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "offsets {} to {} are unsafe: {} between offsets {} and {}",
+            self.start, self.end, self.rule, self.first, self.second
+        )
+    }
+}
+
+/// Watches a stream of levels, checking the last `window` of them as a report every time a level
+/// comes in, and alerting on each bad step once, however many windows it's in. Once there's been
+/// an alert, only the levels from the later side of its bad step on are checked, so a new bad step
+/// is caught straight away even while the old one is still in the window. Until the window fills
+/// up, the levels seen so far are checked.
+///
+/// Nothing but the last two levels is kept. Instead, each direction tracks how far back the window
+/// could start and still be safe, which only ever moves forwards, so each level takes the same time
+/// however long the window is
+#[derive(Debug, Clone)]
+pub struct Monitor {
+    policy: SafetyPolicy,
+    window: u64,
+    /// Whether the dampener gets to remove a level from each window
+    dampened: bool,
+    /// One for each direction the policy allows
+    runs: Vec<Run>,
+    /// The two levels before the latest one, oldest first
+    recent: [Option<u64>; 2],
+    /// How many levels have come in altogether
+    seen: u64,
+    /// Offset of the later level of the last bad step alerted on
+    last_alert: Option<u64>,
+}
+
+/// How the stream is holding up going one way, as it stands after the latest level
+#[derive(Debug, Clone)]
+struct Run {
+    increasing: bool,
+    /// The latest bad step, as the offset of its later level and the rule it breaks
+    last_bad: Option<(u64, Rule)>,
+    /// Earliest offsets that spans ending two levels back, one level back, and at the latest level
+    /// can start from without any bad steps
+    clean_from: [u64; 3],
+    /// Earliest offset a span ending at the latest level can start from without any bad steps,
+    /// once one of the levels before the latest is removed
+    dampened_from: u64,
+}
+
+impl Run {
+    fn new(increasing: bool) -> Self {
+        Self {
+            increasing,
+            last_bad: None,
+            clean_from: [0; 3],
+            dampened_from: 0,
+        }
+    }
+
+    /// Moves on to the level at `offset`, given the two levels before it
+    fn push(
+        &mut self,
+        policy: &SafetyPolicy,
+        offset: u64,
+        [before, previous]: [Option<u64>; 2],
+        level: u64,
+    ) {
+        let [_, clean_before, clean_previous] = self.clean_from;
+        let step = previous.map(|previous| policy.check_step(previous, level, self.increasing));
+
+        let clean_from = match step {
+            Some(Err(rule)) => {
+                self.last_bad = Some((offset, rule));
+                offset
+            }
+            _ => clean_previous,
+        };
+
+        // Either whatever was removed before the previous level stays removed and the step from it
+        // is fine, or the previous level is the one removed and the one before steps over it
+        let keep_previous = match step {
+            Some(Err(_)) => u64::MAX,
+            _ => self.dampened_from,
+        };
+        let remove_previous = match before {
+            Some(before) if policy.is_valid_step(before, level, self.increasing) => clean_before,
+            _ => offset.saturating_sub(1),
+        };
+        self.dampened_from = keep_previous.min(remove_previous);
+        self.clean_from = [clean_before, clean_previous, clean_from];
+    }
+
+    /// Earliest offset a span ending at the latest level can start from and be safe. With the
+    /// dampener, that's also the latest level itself being removed
+    fn safe_from(&self, dampened: bool) -> u64 {
+        let [_, clean_previous, clean_from] = self.clean_from;
+        if dampened {
+            clean_from.min(clean_previous).min(self.dampened_from)
+        } else {
+            clean_from
+        }
+    }
+}
+
+impl Monitor {
+    pub fn new(policy: SafetyPolicy, window: u64, dampened: bool) -> Self {
+        let runs = policy.directions().iter().map(|&i| Run::new(i)).collect();
+        Self {
+            policy,
+            window,
+            dampened,
+            runs,
+            recent: [None; 2],
+            seen: 0,
+            last_alert: None,
+        }
+    }
+
+    /// Whether the dampener can remove a level from a span this long
+    fn can_remove(&self, levels: u64) -> bool {
+        // Removing a level would leave the span too short, so it has to be safe as it is
+        self.dampened && !self.policy.is_too_short(levels.saturating_sub(1) as usize)
+    }
+
+    /// Adds the next level, giving an alert if it made the window unsafe
+    pub fn push(&mut self, level: u64) -> Option<Alert> {
+        let offset = self.seen;
+        self.seen += 1;
+        let window_start = self.seen.saturating_sub(self.window);
+        let start = self
+            .last_alert
+            .map_or(window_start, |last| last.max(window_start));
+        let levels = self.seen - start;
+
+        // Whichever direction kept going longest before this level is the one it broke
+        let dampened_before = self.can_remove(levels - 1);
+        let kept_from = self
+            .runs
+            .iter()
+            .map(|run| run.safe_from(dampened_before))
+            .collect::<Vec<_>>();
+        for run in &mut self.runs {
+            run.push(&self.policy, offset, self.recent, level);
+        }
+        self.recent = [self.recent[1], Some(level)];
+
+        if self.policy.is_too_short(levels as usize) {
+            let rule = Rule::TooShort {
+                levels: levels as usize,
+            };
+            return Some(self.alert(start, rule, start, offset));
+        }
+
+        let dampened = self.can_remove(levels);
+        if self.runs.iter().any(|run| run.safe_from(dampened) <= start) {
+            return None;
+        }
+
+        // Point at the newest bad step, which is what stopped the dampener saving the window
+        let (run, _) = self
+            .runs
+            .iter()
+            .zip(kept_from)
+            .min_by_key(|&(_, from)| from)?;
+        let (second, rule) = run.last_bad?;
+        Some(self.alert(start, rule, second - 1, second))
+    }
+
+    /// Notes an alert for the bad step between `first` and `second`, in the span from `start` up
+    /// to the latest level
+    fn alert(&mut self, start: u64, rule: Rule, first: u64, second: u64) -> Alert {
+        self.last_alert = Some(second);
+        Alert {
+            start,
+            end: self.seen - 1,
+            rule,
+            first,
+            second,
+        }
+    }
+}
+
+/// Reads levels separated by any whitespace, line breaks or not, a chunk at a time. Only the level
+/// being read is held, so the stream can go on forever
+pub struct Levels<R> {
+    reader: R,
+    token: Vec<u8>,
+    start: Location,
+    line: usize,
+    /// Chars so far on the current line
+    column: usize,
+}
+
+/// Reads the levels from a stream
+pub fn levels<R: BufRead>(reader: R) -> Levels<R> {
+    Levels {
+        reader,
+        token: vec![],
+        start: Location::new(0, 0),
+        line: 0,
+        column: 0,
+    }
+}
+
+impl<R> Levels<R> {
+    /// Parses the token which has been read, leaving it empty
+    fn finish_token(&mut self) -> Result<u64, ParseError> {
+        let token = std::mem::take(&mut self.token);
+        std::str::from_utf8(&token)
+            .ok()
+            .and_then(|t| t.parse::<u64>().ok())
+            .ok_or_else(|| ParseError::InvalidLevel {
+                location: self.start,
+                found: String::from_utf8_lossy(&token).into_owned(),
+            })
+    }
+}
+
+impl<R: BufRead> Iterator for Levels<R> {
+    type Item = Result<u64, MonitorError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(e.into())),
+            };
+            if buf.is_empty() {
+                return (!self.token.is_empty()).then(|| self.finish_token().map_err(Into::into));
+            }
+
+            let mut used = 0;
+            let mut finished = false;
+            for &b in buf {
+                used += 1;
+                if b.is_ascii_whitespace() {
+                    finished = !self.token.is_empty();
+                    if b == b'\n' {
+                        self.line += 1;
+                        self.column = 0;
+                    } else {
+                        self.column += 1;
+                    }
+                    if finished {
+                        break;
+                    }
+                    continue;
+                }
+
+                if self.token.is_empty() {
+                    self.start = Location::new(self.line, self.column);
+                }
+                self.token.push(b);
+                // UTF-8 continuation bytes are part of the char before
+                if b & 0xc0 != 0x80 {
+                    self.column += 1;
+                }
+            }
+            self.reader.consume(used);
+
+            if finished {
+                return Some(self.finish_token().map_err(Into::into));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+    use crate::{check_report, dampen, ShortReports};
+
+    fn alerts(levels: &[u64], window: u64, dampened: bool) -> Vec<Alert> {
+        let mut monitor = Monitor::new(SafetyPolicy::default(), window, dampened);
+        levels.iter().filter_map(|&l| monitor.push(l)).collect()
+    }
+
+    #[test]
+    fn alerts_once_per_bad_step() {
+        assert_eq!(
+            alerts(&[1, 2, 3, 4, 5, 9, 10, 11, 12, 13, 12], 4, false),
+            [
+                Alert {
+                    start: 2,
+                    end: 5,
+                    rule: Rule::StepTooLarge { step: 4 },
+                    first: 4,
+                    second: 5,
+                },
+                Alert {
+                    start: 7,
+                    end: 10,
+                    rule: Rule::DirectionChange,
+                    first: 9,
+                    second: 10,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn alerts_on_close_bad_steps() {
+        // 11 -> 20 comes in while 3 -> 9 is still in the window
+        assert_eq!(
+            alerts(&[1, 2, 3, 9, 10, 11, 20, 21, 22, 23, 24], 6, false),
+            [
+                Alert {
+                    start: 0,
+                    end: 3,
+                    rule: Rule::StepTooLarge { step: 6 },
+                    first: 2,
+                    second: 3,
+                },
+                Alert {
+                    start: 3,
+                    end: 6,
+                    rule: Rule::StepTooLarge { step: 9 },
+                    first: 5,
+                    second: 6,
+                },
+            ]
+        );
+        assert_eq!(
+            alerts(&[1, 2, 3, 9, 10, 11, 20, 21, 22, 23, 24], 4, false)
+                .iter()
+                .map(|a| (a.first, a.second))
+                .collect::<Vec<_>>(),
+            [(2, 3), (5, 6)]
+        );
+    }
+
+    #[test]
+    fn dampened() {
+        // Both 3 -> 9 and 9 -> 4 are bad, but the dampener takes out the 9
+        let levels = [1, 2, 3, 9, 4, 5, 6, 7, 8];
+        assert_eq!(alerts(&levels, 4, false).len(), 2);
+        assert_eq!(alerts(&levels, 4, true), []);
+    }
+
+    #[test]
+    fn dampened_points_at_newest_bad_step() {
+        // 3 -> 9 is dampened, so it's 6 -> 13 that makes the window unsafe
+        assert_eq!(
+            alerts(&[1, 2, 3, 9, 4, 5, 6, 13], 10, true),
+            [Alert {
+                start: 0,
+                end: 7,
+                rule: Rule::StepTooLarge { step: 7 },
+                first: 6,
+                second: 7,
+            }]
+        );
+        // Nothing's wrong with 10 -> 11, but it means neither 3 nor 10 can be removed any more
+        assert_eq!(
+            alerts(&[1, 2, 3, 10, 11], 10, true),
+            [Alert {
+                start: 0,
+                end: 4,
+                rule: Rule::StepTooLarge { step: 7 },
+                first: 2,
+                second: 3,
+            }]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_checking_every_window(
+            levels in vec(0_u64..8, 0..40),
+            window in 1_u64..10,
+            dampened in any::<bool>(),
+            allow_plateaus in any::<bool>(),
+            short_reports in prop_oneof![Just(ShortReports::Safe), Just(ShortReports::Unsafe)],
+        ) {
+            let policy = SafetyPolicy {
+                allow_plateaus,
+                short_reports,
+                ..SafetyPolicy::default()
+            };
+            let mut monitor = Monitor::new(policy.clone(), window, dampened);
+            let mut last_alert = None;
+            for (offset, &level) in levels.iter().enumerate() {
+                let alert = monitor.push(level);
+
+                // Check the same span the long way round
+                let window_start = (offset as u64 + 1).saturating_sub(window);
+                let start = last_alert.map_or(window_start, |last: u64| last.max(window_start));
+                let span = &levels[start as usize..=offset];
+                let is_safe = if dampened {
+                    dampen(span, &policy).is_safe()
+                } else {
+                    check_report(span, &policy).is_safe()
+                };
+                prop_assert_eq!(alert.is_none(), is_safe, "offset {}", offset);
+
+                if let Some(alert) = alert {
+                    prop_assert_eq!((alert.start, alert.end), (start, offset as u64));
+                    if !matches!(alert.rule, Rule::TooShort { .. }) {
+                        let (first, second) = (levels[alert.first as usize], levels[alert.second as usize]);
+                        prop_assert_eq!(alert.first + 1, alert.second);
+                        prop_assert!(alert.first >= start);
+                        prop_assert!(policy
+                            .directions()
+                            .iter()
+                            .any(|&increasing| policy.check_step(first, second, increasing) == Err(alert.rule)));
+                    }
+                    last_alert = Some(alert.second);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn levels_across_chunks() {
+        // A tiny buffer splits levels between reads
+        let reader = BufReader::with_capacity(3, &b"  12 345\n\n6\t78 \n9"[..]);
+        let read = levels(reader).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(read, [12, 345, 6, 78, 9]);
+
+        let reader = BufReader::with_capacity(3, "1 2\né3 4".as_bytes());
+        let error = levels(reader).nth(2).unwrap().unwrap_err();
+        assert!(matches!(
+            error,
+            MonitorError::Parse(ParseError::InvalidLevel { location, found })
+                if location == Location::new(1, 0) && found == "é3"
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Which way the levels in a report have to go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    /// Either way, as long as the whole report agrees
+    Either,
+}
+
+/// Whether reports with fewer than two levels, and so no steps to check, count as safe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShortReports {
+    Safe,
+    Unsafe,
+}
+
+/// The rules a report has to follow to be safe
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SafetyPolicy {
+    /// Smallest change allowed between neighbouring levels, not counting plateaus
+    pub min_step: u64,
+    /// Biggest change allowed between neighbouring levels
+    pub max_step: u64,
+    /// Whether neighbouring levels can be the same
+    pub allow_plateaus: bool,
+    pub direction: Direction,
+    /// What to make of empty and single-level reports
+    pub short_reports: ShortReports,
+}
+
+#[derive(Debug, Error)]
+pub enum PolicyError {
+    #[error("invalid safety policy: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("invalid safety policy: min_step {min_step} is bigger than max_step {max_step}")]
+    EmptyStepRange { min_step: u64, max_step: u64 },
+}
+
+/// The puzzle's rules: steps of 1 to 3, always going the same way. Short reports break none of
+/// them, so they're safe
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        Self {
+            min_step: 1,
+            max_step: 3,
+            allow_plateaus: false,
+            direction: Direction::Either,
+            short_reports: ShortReports::Safe,
+        }
+    }
+}
+
+impl SafetyPolicy {
+    /// Reads a policy from TOML, with anything left out taking the puzzle's value
+    pub fn from_toml(text: &str) -> Result<Self, PolicyError> {
+        let policy = toml::from_str::<Self>(text)?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Makes sure some step can actually pass
+    pub fn validate(&self) -> Result<(), PolicyError> {
+        if self.min_step > self.max_step {
+            return Err(PolicyError::EmptyStepRange {
+                min_step: self.min_step,
+                max_step: self.max_step,
+            });
+        }
+        Ok(())
+    }
+
+    /// The directions a report can go, as whether it's increasing
+    pub(crate) fn directions(&self) -> &'static [bool] {
+        match self.direction {
+            Direction::Increasing => &[true],
+            Direction::Decreasing => &[false],
+            Direction::Either => &[true, false],
+        }
+    }
+
+    /// Whether a report with this many levels is too short to be safe, whatever they are
+    pub fn is_too_short(&self, levels: usize) -> bool {
+        levels < 2 && self.short_reports == ShortReports::Unsafe
+    }
+
+    /// Checks the step between two neighbouring levels, for a report going up or down, giving the
+    /// rule it breaks if it isn't allowed
+    pub fn check_step(&self, a: u64, b: u64, increasing: bool) -> Result<(), Rule> {
+        if a == b {
+            return if self.allow_plateaus {
+                Ok(())
+            } else {
+                Err(Rule::Plateau)
+            };
+        }
+
+        let (from, to) = if increasing { (a, b) } else { (b, a) };
+        if to < from {
+            return Err(Rule::DirectionChange);
+        }
+        let step = to - from;
+        if step < self.min_step {
+            Err(Rule::StepTooSmall { step })
+        } else if step > self.max_step {
+            Err(Rule::StepTooLarge { step })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks the step between two neighbouring levels, for a report going up or down
+    pub fn is_valid_step(&self, a: u64, b: u64, increasing: bool) -> bool {
+        self.check_step(a, b, increasing).is_ok()
+    }
+}
+
+/// A rule broken by the step between two levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// The levels are the same, and plateaus aren't allowed
+    Plateau,
+    /// The levels go the other way to the rest of the report
+    DirectionChange,
+    StepTooSmall {
+        step: u64,
+    },
+    StepTooLarge {
+        step: u64,
+    },
+    /// There are too few levels to have any steps, and short reports aren't allowed
+    TooShort {
+        levels: usize,
+    },
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Plateau => write!(f, "plateau"),
+            Rule::DirectionChange => write!(f, "change of direction"),
+            Rule::StepTooSmall { step } => write!(f, "step of {} is too small", step),
+            Rule::StepTooLarge { step } => write!(f, "step of {} is too large", step),
+            Rule::TooShort { levels: 1 } => write!(f, "only 1 level"),
+            Rule::TooShort { levels } => write!(f, "only {} levels", levels),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_toml() {
+        let policy = SafetyPolicy::from_toml("max_step = 5\ndirection = \"increasing\"\n").unwrap();
+        assert_eq!(
+            policy,
+            SafetyPolicy {
+                max_step: 5,
+                direction: Direction::Increasing,
+                ..SafetyPolicy::default()
+            }
+        );
+
+        assert!(SafetyPolicy::from_toml("max_steps = 5").is_err());
+        assert!(SafetyPolicy::from_toml("min_step = 4").is_err());
+    }
+
+    #[test]
+    fn check_step() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(policy.check_step(1, 3, true), Ok(()));
+        assert_eq!(policy.check_step(3, 1, false), Ok(()));
+        assert_eq!(policy.check_step(3, 3, true), Err(Rule::Plateau));
+        assert_eq!(policy.check_step(3, 1, true), Err(Rule::DirectionChange));
+        assert_eq!(
+            policy.check_step(1, 5, true),
+            Err(Rule::StepTooLarge { step: 4 })
+        );
+        assert_eq!(
+            SafetyPolicy {
+                min_step: 2,
+                ..policy
+            }
+            .check_step(2, 1, false),
+            Err(Rule::StepTooSmall { step: 1 })
+        );
+    }
+}
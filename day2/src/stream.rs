@@ -0,0 +1,158 @@
+use std::io::{self, BufRead, Write};
+
+use rayon::prelude::*;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    check_report, dampen, is_blank, parse_line, Dampened, ParseError, Safety, SafetyPolicy,
+};
+
+/// Most lines read in before they're checked in parallel and written out
+const BATCH_SIZE: usize = 1 << 16;
+
+#[derive(Debug, Error)]
+pub enum StreamError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// One CSV row. Reports and levels are counted from 1
+#[derive(Debug, Serialize)]
+struct Row {
+    index: usize,
+    status: &'static str,
+    /// What's wrong with the report as it stands
+    reason: Option<String>,
+    /// The level the dampener removed to make the report safe
+    removed_level: Option<usize>,
+}
+
+fn evaluate(
+    index: usize,
+    line: &str,
+    policy: &SafetyPolicy,
+    dampened: bool,
+) -> Result<Row, ParseError> {
+    let report = parse_line(index, line)?;
+    let (status, reason, removed_level) = match check_report(&report, policy) {
+        Safety::Safe => ("safe", None, None),
+        Safety::Unsafe(violation) => match dampened.then(|| dampen(&report, policy)) {
+            Some(Dampened::SafeWithout(removed)) => {
+                ("dampened", Some(violation.to_string()), Some(removed + 1))
+            }
+            _ => ("unsafe", Some(violation.to_string()), None),
+        },
+    };
+
+    Ok(Row {
+        index: index + 1,
+        status,
+        reason,
+        removed_level,
+    })
+}
+
+/// Checks a batch of numbered lines in parallel and writes their rows in order, leaving the batch
+/// empty. Gives how many were safe
+fn write_batch(
+    batch: &mut Vec<(usize, String)>,
+    writer: &mut csv::Writer<impl Write>,
+    policy: &SafetyPolicy,
+    dampened: bool,
+) -> Result<usize, StreamError> {
+    let rows = batch
+        .par_iter()
+        .map(|(i, line)| evaluate(*i, line, policy, dampened))
+        .collect::<Result<Vec<_>, _>>()?;
+    batch.clear();
+
+    let safe = rows.iter().filter(|row| row.status != "unsafe").count();
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    Ok(safe)
+}
+
+/// Checks the reports in `input` a batch at a time, spread across threads, writing a CSV row for
+/// each one in order. Only one batch is held in memory at once. Blank lines are skipped like in
+/// `parse`, but each row's index is still the report's line number. Gives how many reports were
+/// safe
+pub fn write_csv(
+    input: impl BufRead,
+    output: impl Write,
+    policy: &SafetyPolicy,
+    dampened: bool,
+) -> Result<usize, StreamError> {
+    let mut writer = csv::Writer::from_writer(output);
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+    let mut safe = 0;
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        if is_blank(&line) {
+            continue;
+        }
+
+        batch.push((i, line));
+        if batch.len() >= BATCH_SIZE {
+            safe += write_batch(&mut batch, &mut writer, policy, dampened)?;
+        }
+    }
+    safe += write_batch(&mut batch, &mut writer, policy, dampened)?;
+
+    writer.flush()?;
+    Ok(safe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_csv() {
+        let mut output = vec![];
+        let input = format!("{}\n\n", include_str!("../example.txt"));
+        let safe = write_csv(
+            input.as_bytes(),
+            &mut output,
+            &SafetyPolicy::default(),
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(safe, 4);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "index,status,reason,removed_level\n\
+             1,safe,,\n\
+             2,unsafe,step of 5 is too large between levels 2 and 3,\n\
+             3,unsafe,step of 4 is too large between levels 3 and 4,\n\
+             4,dampened,change of direction between levels 2 and 3,2\n\
+             5,dampened,plateau between levels 3 and 4,3\n\
+             6,safe,,\n"
+        );
+    }
+
+    #[test]
+    fn blank_lines() {
+        let mut output = vec![];
+        let safe = write_csv(
+            &b"1 2\n\n \n3\n\n"[..],
+            &mut output,
+            &SafetyPolicy::default(),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(safe, 2);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "index,status,reason,removed_level\n1,safe,,\n4,safe,,\n"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    convert::Infallible,
+    io::{self, Read},
+};
+
+use crate::Command;
+
+/// Bytes read from the stream at a time
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// How much of a command has been seen so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Start,
+    M,
+    Mu,
+    Mul,
+    /// Reading the first operand of `mul(`
+    Left {
+        value: u64,
+        digits: u8,
+    },
+    /// Reading the second operand, after the comma
+    Right {
+        left: u64,
+        value: u64,
+        digits: u8,
+    },
+    D,
+    Do,
+    /// `do(`
+    DoOpen,
+    Don,
+    DonQuote,
+    Dont,
+    /// `don't(`
+    DontOpen,
+}
+
+/// Picks commands out of corrupted memory a byte at a time, matching exactly what the regex in
+/// `parse` does, except that only ASCII digits count. Nothing is ever looked back at, so the
+/// input can be split anywhere
+#[derive(Debug, Clone)]
+pub struct Lexer {
+    state: State,
+}
+
+impl Default for Lexer {
+    fn default() -> Self {
+        Self {
+            state: State::Start,
+        }
+    }
+}
+
+impl Lexer {
+    /// Takes the next byte, giving the command it finishes if there is one
+    pub fn push(&mut self, byte: u8) -> Option<Command> {
+        use State::*;
+
+        let digit = byte.is_ascii_digit().then(|| u64::from(byte - b'0'));
+        let (state, command) = match (self.state, byte, digit) {
+            (M, b'u', _) => (Mu, None),
+            (Mu, b'l', _) => (Mul, None),
+            (Mul, b'(', _) => (
+                Left {
+                    value: 0,
+                    digits: 0,
+                },
+                None,
+            ),
+            (Left { value, digits }, _, Some(d)) if digits < 3 => (
+                Left {
+                    value: value * 10 + d,
+                    digits: digits + 1,
+                },
+                None,
+            ),
+            (Left { value, digits }, b',', _) if digits > 0 => (
+                Right {
+                    left: value,
+                    value: 0,
+                    digits: 0,
+                },
+                None,
+            ),
+            (
+                Right {
+                    left,
+                    value,
+                    digits,
+                },
+                _,
+                Some(d),
+            ) if digits < 3 => (
+                Right {
+                    left,
+                    value: value * 10 + d,
+                    digits: digits + 1,
+                },
+                None,
+            ),
+            (
+                Right {
+                    left,
+                    value,
+                    digits,
+                },
+                b')',
+                _,
+            ) if digits > 0 => (Start, Some(Command::Mul(left, value))),
+            (D, b'o', _) => (Do, None),
+            (Do, b'(', _) => (DoOpen, None),
+            (DoOpen, b')', _) => (Start, Some(Command::Do)),
+            (Do, b'n', _) => (Don, None),
+            (Don, b'\'', _) => (DonQuote, None),
+            (DonQuote, b't', _) => (Dont, None),
+            (Dont, b'(', _) => (DontOpen, None),
+            (DontOpen, b')', _) => (Start, Some(Command::Dont)),
+            // No command has an `m` or `d` after its first byte, so a command which broke off can
+            // only be followed by one starting at this byte
+            (_, b'm', _) => (M, None),
+            (_, b'd', _) => (D, None),
+            _ => (Start, None),
+        };
+
+        self.state = state;
+        command
+    }
+}
+
+/// Picks the commands out of the corrupted memory without copying or allocating anything
+pub fn lex(input: &[u8]) -> impl Iterator<Item = Command> + '_ {
+    let mut lexer = Lexer::default();
+    input.iter().filter_map(move |&b| lexer.push(b))
+}
+
+/// Same as `parse`, but with the lexer, for the runner
+pub fn parse_lexer(input: &str) -> Result<Vec<Command>, Infallible> {
+    Ok(lex(input.as_bytes()).collect())
+}
+
+/// Reads the commands out of a stream, a chunk at a time, so it takes the same memory however
+/// long the stream is
+pub struct Commands<R> {
+    reader: R,
+    lexer: Lexer,
+    chunk: Box<[u8]>,
+    /// The unread part of the chunk
+    start: usize,
+    end: usize,
+}
+
+/// Reads the commands out of a stream
+pub fn commands<R: Read>(reader: R) -> Commands<R> {
+    Commands {
+        reader,
+        lexer: Lexer::default(),
+        chunk: vec![0; CHUNK_SIZE].into_boxed_slice(),
+        start: 0,
+        end: 0,
+    }
+}
+
+impl<R: Read> Iterator for Commands<R> {
+    type Item = io::Result<Command>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while self.start < self.end {
+                let byte = self.chunk[self.start];
+                self.start += 1;
+                if let Some(command) = self.lexer.push(byte) {
+                    return Some(Ok(command));
+                }
+            }
+
+            match self.reader.read(&mut self.chunk) {
+                Ok(0) => return None,
+                Ok(n) => {
+                    self.start = 0;
+                    self.end = n;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*, sample::select};
+
+    use super::*;
+    use crate::parse;
+
+    const EXAMPLE_PART2: &str = include_str!("../example_part2.txt");
+
+    #[test]
+    fn matches_regex() {
+        let inputs = [
+            EXAMPLE_PART2,
+            "mumul(1,2)mul(1234,5)mul(12,345)mul(,1)mul(1,)mul(1 ,2)",
+            "dodo()don'don't()do(don't())mmul(3,4)dmul(5,6)",
+            "mul(1,2mul(3,4)mul(999,999)mul(0,07))",
+        ];
+        for input in inputs {
+            assert_eq!(
+                lex(input.as_bytes()).collect::<Vec<_>>(),
+                parse(input).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn across_chunks() {
+        // Gives a few bytes per read, so commands get split between chunks
+        struct Trickle<'a>(&'a [u8]);
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let n = buf.len().min(3).min(self.0.len());
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+
+        let read = commands(Trickle(EXAMPLE_PART2.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(read, parse(EXAMPLE_PART2).unwrap());
+    }
+
+    proptest! {
+        /// Random bits of commands stuck together break off in all sorts of places
+        #[test]
+        fn matches_regex_on_fragments(fragments in vec(select(&[
+            "mul(", "do()", "don't()", "mul", "do", "don't", "m", "u", "l", "d", "o", "n", "'",
+            "t", "(", ")", ",", "1", "23", "456", "7890", " ", "x",
+        ][..]), 0..40)) {
+            let input = fragments.concat();
+            prop_assert_eq!(lex(input.as_bytes()).collect::<Vec<_>>(), parse(&input).unwrap());
+        }
+    }
+}
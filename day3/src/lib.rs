@@ -0,0 +1,109 @@
+use std::sync::LazyLock;
+
+use aoc_common::Location;
+use regex::{Match, Regex};
+use thiserror::Error;
+use tracing::trace;
+
+mod lexer;
+
+pub use lexer::{commands, lex, parse_lexer, Commands, Lexer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Do,
+    Dont,
+    Mul(u64, u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("{location}: expected a number of up to 3 digits, found {found:?}")]
+    InvalidNumber { location: Location, found: String },
+}
+
+/// Matches "mul(a, b)", "do()", "don't()". Built once, rather than on every parse
+static COMMAND: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|(do\(\))|(don't\(\))").unwrap());
+
+/// Picks the commands out of the corrupted memory with a regex. See `lex` for a faster way which
+/// doesn't need the whole input at once
+pub fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+    // Parses a mul operand, pointing at where it is in the input if it's broken. \d also matches
+    // non-ASCII digits, which won't parse
+    let parse_number = |m: Match| {
+        m.as_str()
+            .parse::<u64>()
+            .map_err(|_| ParseError::InvalidNumber {
+                location: Location::of_offset(input, m.start()),
+                found: m.as_str().to_string(),
+            })
+    };
+
+    COMMAND
+        // Apply regex
+        .captures_iter(input)
+        // Parse capture groups into Command enum. Exactly one of the alternatives will have
+        // matched, so the first group that's there tells us which one
+        .map(|c| match (c.get(1), c.get(2), c.get(3)) {
+            (Some(a), Some(b), _) => Ok(Command::Mul(parse_number(a)?, parse_number(b)?)),
+            (_, _, Some(_)) => Ok(Command::Do),
+            _ => Ok(Command::Dont),
+        })
+        .collect()
+}
+
+pub fn solve_part1(input: &[Command]) -> u64 {
+    input
+        .iter()
+        .inspect(|c| trace!(command = ?c))
+        .filter_map(|c| match c {
+            Command::Mul(a, b) => Some(a * b),
+            _ => None,
+        })
+        .sum::<u64>()
+}
+
+pub fn solve_part2(input: &[Command]) -> u64 {
+    let (_, sum) = input
+        .iter()
+        // Accumulate commands
+        .fold((true, 0), |(enabled, sum), c| match (c, enabled) {
+            (Command::Do, _) => (true, sum),
+            (Command::Dont, _) => (false, sum),
+            (Command::Mul(a, b), true) => (enabled, sum + a * b),
+            (Command::Mul(_, _), false) => (enabled, sum),
+        });
+
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_PART1: &str = include_str!("../example_part1.txt");
+    const EXAMPLE_PART2: &str = include_str!("../example_part2.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(&parse(EXAMPLE_PART1).unwrap()), 161);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(&parse(EXAMPLE_PART2).unwrap()), 48);
+    }
+
+    #[test]
+    fn invalid_number_location() {
+        // \d matches an Arabic-Indic digit, which takes two bytes but is one column
+        assert_eq!(
+            parse("mul(1,2)\nxé mul(3,\u{0661}2)").unwrap_err(),
+            ParseError::InvalidNumber {
+                location: Location::new(1, 9),
+                found: "\u{0661}2".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use aoc_common::{Direction8, Grid, GridError};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+}
+
+/// Parses the word search into a grid of letters
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Ok(Grid::parse(input, |c| c)?)
+}
+
+/// Counts how many times the given pattern exists at the given point, in any direction
+fn grid_regex(grid: &Grid<char>, pattern: &[char], grid_point: (usize, usize)) -> usize {
+    Direction8::ALL
+        .iter()
+        .map(|direction| {
+            // Test if the pattern matches along this direction, the ray stops short if we go out
+            // of bounds which fails the match
+            grid.ray(grid_point, direction.offset())
+                .take(pattern.len())
+                .map(|(_, test_letter)| test_letter)
+                .eq(pattern)
+        })
+        // Count matches
+        .filter(|m| *m)
+        .count()
+}
+
+pub fn solve_part1(grid: &Grid<char>) -> usize {
+    let regex = "XMAS".chars().collect::<Vec<_>>();
+
+    grid.points()
+        // Test the regex at this point
+        .map(|point| grid_regex(grid, &regex, point))
+        .sum::<usize>()
+}
+
+/// Rotates the kernel 90 degrees clockwise
+fn rotate_kernel(kernel: &[Vec<Option<char>>]) -> Vec<Vec<Option<char>>> {
+    (0..kernel[0].len())
+        .map(|i| {
+            (0..kernel.len())
+                .rev()
+                .map(|j| kernel[j][i])
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Tests the kernel against the grid to see if it matches
+fn test_kernel(grid: &[&[char]], kernel: &[Vec<Option<char>>]) -> bool {
+    grid.iter()
+        // Loop over the grid & kernel in pairs
+        .flat_map(|row| row.iter())
+        .zip(kernel.iter().flatten())
+        // Compare the chars, None in the kernel counts as a wildcard
+        .all(|(test_letter, pattern_letter)| {
+            pattern_letter.map(|p| *test_letter == p).unwrap_or(true)
+        })
+}
+
+/// Counts how many matches the kernel gets on this grid section
+fn kernel_regex(grid: &[&[char]], kernel: &[Vec<Option<char>>]) -> usize {
+    // Generate 4 different orientations of kernels and test them against the grid
+    let (_, matches) = (0..4).fold((kernel.to_vec(), 0), |(k, count), _| {
+        let count = if test_kernel(grid, &k) {
+            count + 1
+        } else {
+            count
+        };
+
+        (rotate_kernel(&k), count)
+    });
+
+    matches
+}
+
+pub fn solve_part2(grid: &Grid<char>) -> usize {
+    // Kernel used to match against. None counts as a wildcard
+    let kernel = vec![
+        vec![Some('M'), None, Some('S')],
+        vec![None, Some('A'), None],
+        vec![Some('M'), None, Some('S')],
+    ];
+
+    grid.points()
+        // Only use points where the kernel fits on the grid
+        .filter(|&(i, j)| i + kernel.len() <= grid.height() && j + kernel[0].len() <= grid.width())
+        // Create a grid slice in this location
+        .map(|(i, j)| {
+            (i..i + kernel.len())
+                .map(|y| &grid.row(y)[j..j + kernel[0].len()])
+                .collect::<Vec<_>>()
+        })
+        // Test the kernel on the grid slice
+        .map(|grid_section| kernel_regex(&grid_section, &kernel))
+        // Add em up
+        .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Location;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(&parse(EXAMPLE).unwrap()), 18);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(&parse(EXAMPLE).unwrap()), 9);
+    }
+
+    #[test]
+    fn ragged_row_location() {
+        assert_eq!(
+            parse("XMAS\nSAMX\nXMA\n").unwrap_err(),
+            ParseError::Grid(GridError::RaggedRow {
+                location: Location::new(2, 3),
+                expected: 4,
+                found: 3,
+            })
+        );
+    }
+}
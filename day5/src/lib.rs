@@ -0,0 +1,247 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::Location;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    /// LUT of page -> pages which aren't allowed to come before it
+    pub disqualifiers: HashMap<u64, HashSet<u64>>,
+    /// The lists of pages in each update
+    pub tests: Vec<Vec<u64>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("{location}: expected '|' between the two pages of an ordering rule")]
+    MissingSeparator { location: Location },
+    #[error("{location}: expected a page number, found {found:?}")]
+    InvalidPage { location: Location, found: String },
+    #[error("{location}: rule {before}|{after} contradicts the earlier rule {after}|{before}")]
+    ConflictingRules {
+        location: Location,
+        before: u64,
+        after: u64,
+    },
+    #[error("{location}: rule {page}|{page} puts a page before itself")]
+    SelfRule { location: Location, page: u64 },
+    #[error("{location}: the rules between pages {pages:?} go round in a cycle")]
+    CyclicRules { location: Location, pages: Vec<u64> },
+}
+
+/// Parses a page number which was sliced out of the given line
+fn parse_page(line_index: usize, line: &str, page: &str) -> Result<u64, ParseError> {
+    page.parse::<u64>().map_err(|_| ParseError::InvalidPage {
+        location: Location::of_token(line_index, line, page),
+        found: page.to_string(),
+    })
+}
+
+/// Parses the ordering rules and the updates, which are separated by a blank line
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let lines = input.lines().enumerate().collect::<Vec<_>>();
+    let (rules, tests) = match lines.iter().position(|(_, l)| l.is_empty()) {
+        Some(blank) => (&lines[..blank], &lines[blank + 1..]),
+        None => (&lines[..], &[][..]),
+    };
+
+    // Construct a LUT which flags immediate fails
+    let mut disqualifiers = HashMap::<_, HashSet<_>>::new();
+    for &(i, l) in rules {
+        // Split each constraint
+        let (before, after) = l.split_once("|").ok_or_else(|| {
+            // Point at wherever the first page stops
+            let end = l.find(|c: char| !c.is_ascii_digit()).unwrap_or(l.len());
+            ParseError::MissingSeparator {
+                location: Location::of_token(i, l, &l[end..]),
+            }
+        })?;
+        let (before, after) = (parse_page(i, l, before)?, parse_page(i, l, after)?);
+
+        // Rules which can't all be followed would leave the sort in part 2 with no right answer
+        let location = Location::new(i, 0);
+        if before == after {
+            return Err(ParseError::SelfRule {
+                location,
+                page: before,
+            });
+        }
+        if disqualifiers
+            .get(&after)
+            .is_some_and(|pages| pages.contains(&before))
+        {
+            return Err(ParseError::ConflictingRules {
+                location,
+                before,
+                after,
+            });
+        }
+
+        disqualifiers.entry(before).or_default().insert(after);
+    }
+
+    let tests = tests
+        .iter()
+        .filter(|(_, l)| !l.is_empty())
+        // Parse each test into pages
+        .map(|&(i, l)| {
+            let test = l
+                .split(',')
+                .map(|page| parse_page(i, l, page))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Rules can go round in a cycle overall, as long as no update needs all of them
+            order(&disqualifiers, &test).map_err(|pages| ParseError::CyclicRules {
+                location: Location::new(i, 0),
+                pages,
+            })?;
+
+            Ok(test)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Input {
+        disqualifiers,
+        tests,
+    })
+}
+
+/// Checks that all pages follow the right ordering rules
+fn is_ordered(disqualifiers: &HashMap<u64, HashSet<u64>>, test: &[u64]) -> bool {
+    test.iter().enumerate().all(|(i, page)| {
+        // Make sure that there's no previous pages that would break the ordering rule
+        disqualifiers
+            .get(page)
+            .map(|disallowed_pages| {
+                disallowed_pages.is_disjoint(&test[..i].iter().copied().collect())
+            })
+            .unwrap_or(true)
+    })
+}
+
+/// Puts the pages in an order that follows every rule between them, with pages that have no rules
+/// between them kept in the order they were. If there's no such order, gives back the pages that
+/// couldn't be placed
+fn order(disqualifiers: &HashMap<u64, HashSet<u64>>, test: &[u64]) -> Result<Vec<u64>, Vec<u64>> {
+    let must_precede = |a: &u64, b: &u64| {
+        disqualifiers
+            .get(a)
+            .is_some_and(|disallowed_pages| disallowed_pages.contains(b))
+    };
+
+    let mut left = test.to_vec();
+    let mut ordered = Vec::with_capacity(test.len());
+    while !left.is_empty() {
+        // Take the first page which none of the others have to come before
+        let Some(i) = left
+            .iter()
+            .position(|b| !left.iter().any(|a| must_precede(a, b)))
+        else {
+            return Err(left);
+        };
+        ordered.push(left.remove(i));
+    }
+
+    Ok(ordered)
+}
+
+pub fn solve_part2(input: &Input) -> u64 {
+    let disqualifiers = &input.disqualifiers;
+
+    input
+        .tests
+        .iter()
+        // Get rid of passed tests
+        .filter(|test| !is_ordered(disqualifiers, test))
+        // Fix the ordering of the pages by sorting them topologically by their constraints
+        .map(|test| order(disqualifiers, test).expect("parse rejects cyclic rules"))
+        // Grab the middle page
+        .map(|test| test[test.len() / 2])
+        // Add em up
+        .sum::<u64>()
+}
+
+pub fn solve_part1(input: &Input) -> u64 {
+    input
+        .tests
+        .iter()
+        // Get rid of failed tests
+        .filter(|test| is_ordered(&input.disqualifiers, test))
+        // Grab the middle page
+        .map(|test| test[test.len() / 2])
+        // Add em up
+        .sum::<u64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(&parse(EXAMPLE).unwrap()), 143);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(&parse(EXAMPLE).unwrap()), 123);
+    }
+
+    #[test]
+    fn invalid_page_location() {
+        assert_eq!(
+            parse("47|53\n97|x3\n").unwrap_err(),
+            ParseError::InvalidPage {
+                location: Location::new(1, 3),
+                found: "x3".to_string(),
+            }
+        );
+        assert_eq!(
+            parse("47|53\n\n75,4.7,61\n").unwrap_err(),
+            ParseError::InvalidPage {
+                location: Location::new(2, 3),
+                found: "4.7".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn conflicting_rules() {
+        assert_eq!(
+            parse("1|2\n3|4\n2|1\n\n1,2\n").unwrap_err(),
+            ParseError::ConflictingRules {
+                location: Location::new(2, 0),
+                before: 2,
+                after: 1,
+            }
+        );
+        assert_eq!(
+            parse("1|2\n2|3\n3|1\n\n4,3,2,1\n").unwrap_err(),
+            ParseError::CyclicRules {
+                location: Location::new(4, 0),
+                pages: vec![3, 2, 1],
+            }
+        );
+        assert_eq!(
+            parse("1|2\n5|5\n").unwrap_err(),
+            ParseError::SelfRule {
+                location: Location::new(1, 0),
+                page: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn cycles_outside_updates() {
+        // A cycle across every rule is fine, so long as no update has all of its pages
+        let input = parse("1|2\n2|3\n3|1\n\n2,1,3\n3,2\n1,9,2\n").unwrap_err();
+        assert!(matches!(input, ParseError::CyclicRules { .. }));
+
+        let input = parse("1|2\n2|3\n3|1\n\n3,2\n2,1\n9,2,1\n").unwrap();
+        assert_eq!(solve_part1(&input), 0);
+        // Pages without rules between them keep their order
+        assert_eq!(solve_part2(&input), 3 + 2 + 1);
+    }
+}
@@ -0,0 +1,325 @@
+use std::collections::HashSet;
+
+use aoc_common::{geometry::checked_move, Direction, Grid, GridError, Location, Vec2};
+use itertools::{FoldWhile, Itertools};
+use thiserror::Error;
+use tracing::{debug, trace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Position {
+    y: usize,
+    x: usize,
+    facing: Direction,
+}
+
+impl Position {
+    /// Moves one step in the given direction if possible, keeping our facing
+    fn step(&self, direction: Direction, map_size: (usize, usize)) -> Option<Self> {
+        let (y, x) = checked_move((self.y, self.x), direction.offset(), map_size)?;
+
+        Some(Self { y, x, ..*self })
+    }
+
+    /// Goes forwards one step, if possible
+    fn move_forward(&self, map_size: (usize, usize)) -> Option<Self> {
+        self.step(self.facing, map_size)
+    }
+
+    /// Goes left one step if possible
+    fn move_left(&self, map_size: (usize, usize)) -> Option<Self> {
+        self.step(self.facing.turn_left(), map_size)
+    }
+
+    fn turn_left(&self) -> Self {
+        Self {
+            facing: self.facing.turn_left(),
+            ..*self
+        }
+    }
+}
+
+/// The lab the guard is patrolling
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    /// (height, width)
+    size: (usize, usize),
+    guard: Position,
+    obstacles: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("{location}: expected one of '.', '#' or '^', found {found:?}")]
+    UnexpectedTile { location: Location, found: char },
+    #[error("expected a guard ('^') somewhere on the map, found none")]
+    MissingGuard,
+    #[error(transparent)]
+    Grid(#[from] GridError),
+}
+
+/// Loads the map size, the guard and the obstacles from the input
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    let grid = Grid::try_parse(input, |location, c| match c {
+        '.' | '#' | '^' => Ok(c),
+        _ => Err(ParseError::UnexpectedTile { location, found: c }),
+    })?;
+
+    // Load all the objects
+    let objects = grid
+        .iter()
+        .filter(|(_, c)| **c != '.')
+        .map(|((y, x), c)| (y, x, *c))
+        .collect::<Vec<_>>();
+
+    // Find the guard
+    let guard = objects
+        .iter()
+        .find(|(_, _, c)| *c == '^')
+        .map(|&(y, x, _)| Position {
+            y,
+            x,
+            facing: Direction::Up,
+        })
+        .ok_or(ParseError::MissingGuard)?;
+
+    // Pull out the obstacles
+    let obstacles = objects
+        .into_iter()
+        .filter(|(_, _, c)| *c == '#')
+        .map(|(y, x, _)| (y, x))
+        .collect::<Vec<_>>();
+
+    Ok(Map {
+        size: grid.size(),
+        guard,
+        obstacles,
+    })
+}
+
+/// Walks the guard until they leave the map, returning every tile they step on in order, and where
+/// they were each time they turned
+fn walk(
+    (map_height, map_width): (usize, usize),
+    guard: Position,
+    obstacles: &[(usize, usize)],
+) -> (Vec<(usize, usize)>, Vec<Position>) {
+    let (steps, _, turning_points) = (0..)
+        .fold_while(
+            (vec![(guard.y, guard.x)], guard, vec![]),
+            |(mut steps, mut guard, mut turning_points), _| {
+                let next_bonk = obstacles
+                    .iter()
+                    // Filer only obstacles that are in front of the guard
+                    .filter(|&&(y, x)| match guard.facing {
+                        Direction::Up => y < guard.y && x == guard.x,
+                        Direction::Right => y == guard.y && x > guard.x,
+                        Direction::Down => y > guard.y && x == guard.x,
+                        Direction::Left => y == guard.y && x < guard.x,
+                    })
+                    // And then get the closest
+                    .min_by_key(|&&p| Vec2::from(p).manhattan(Vec2::from((guard.y, guard.x))))
+                    .copied();
+
+                // If there's no obstacle left, note it and put a fake one down
+                let is_finished = next_bonk.is_none();
+                let (y, x) = next_bonk.unwrap_or_else(|| match guard.facing {
+                    Direction::Up => (0, guard.x),
+                    Direction::Right => (guard.y, map_width - 1),
+                    Direction::Down => (map_height - 1, guard.x),
+                    Direction::Left => (guard.y, 0),
+                });
+
+                // Generate the list of steps the guard will take this time
+                let guard_steps: Vec<_> = match guard.facing {
+                    Direction::Up => (y + 1..guard.y).rev().map(|y| (y, guard.x)).collect(),
+                    Direction::Right => (guard.x + 1..x).map(|x| (guard.y, x)).collect(),
+                    Direction::Down => (guard.y + 1..y).map(|y| (y, guard.x)).collect(),
+                    Direction::Left => (x + 1..guard.x).rev().map(|x| (guard.y, x)).collect(),
+                };
+                // And add them to the list we're folding up
+                steps.extend(guard_steps.iter());
+
+                // Update the guard
+                (guard.y, guard.x) = steps[steps.len() - 1];
+                turning_points.push(guard);
+                guard.facing = guard.facing.turn_right();
+
+                // If we're finished, also add the final step where the fake obstacle is
+                if is_finished {
+                    steps.push((y, x));
+                }
+
+                if is_finished {
+                    FoldWhile::Done((steps, guard, turning_points))
+                } else {
+                    FoldWhile::Continue((steps, guard, turning_points))
+                }
+            },
+        )
+        .into_inner();
+
+    (steps, turning_points)
+}
+
+/// Walks the guard until they leave the map, returning every unique tile they step on
+fn patrol(
+    map_size: (usize, usize),
+    guard: Position,
+    obstacles: &[(usize, usize)],
+) -> Vec<(usize, usize)> {
+    let (mut steps, _) = walk(map_size, guard, obstacles);
+
+    // Deduplicate steps
+    steps.sort();
+    steps.dedup();
+
+    steps
+}
+
+/// Walks the guard step by step, returning true if they leave the map or false if they get
+/// stuck in a loop
+fn escapes(
+    map_size: (usize, usize),
+    mut guard: Position,
+    obstacles: &HashSet<(usize, usize)>,
+) -> bool {
+    let mut seen = HashSet::new();
+    loop {
+        // Being in the same place facing the same way twice means we're going round in circles
+        if !seen.insert(guard) {
+            return false;
+        }
+
+        match guard.move_forward(map_size) {
+            None => return true,
+            Some(next) if obstacles.contains(&(next.y, next.x)) => {
+                guard.facing = guard.facing.turn_right()
+            }
+            Some(next) => guard = next,
+        }
+    }
+}
+
+pub fn solve_part1(map: &Map) -> usize {
+    patrol(map.size, map.guard, &map.obstacles).len()
+}
+
+/// Brute force - place an obstacle at every step, and re-run the simulation
+pub fn solve_part2(map: &Map) -> usize {
+    let Map {
+        size: map_size,
+        guard,
+        ref obstacles,
+    } = *map;
+    let steps = patrol(map_size, guard, obstacles);
+    let mut obstacles = obstacles.iter().copied().collect::<HashSet<_>>();
+
+    steps
+        .into_iter()
+        // Can't place an obstacle on top of the guard
+        .filter(|&step| step != (guard.y, guard.x))
+        .filter(|&step| {
+            obstacles.insert(step);
+            let is_loop = !escapes(map_size, guard, &obstacles);
+            obstacles.remove(&step);
+
+            is_loop
+        })
+        .count()
+}
+
+/// Unfinished walk back approach to part 2. Only finds the turning points where we could branch
+/// so far, and returns how many of those there are, so it isn't used until it's finished
+#[allow(dead_code)]
+fn walk_back_branches(map: &Map) -> usize {
+    let Map {
+        size: map_size,
+        guard,
+        ref obstacles,
+    } = *map;
+    let (steps, turning_points) = walk(map_size, guard, obstacles);
+
+    debug!(steps = steps.len(), "walked the patrol");
+
+    // Constraints for part 2:
+    // 1) Obstacle must be placed on a tile that a guard walks on, except the first one
+    // 2) To detect a loop, a guard must either:
+    //      a) Walk onto a previously stepped on tile, facing the same direction
+    //      b) Walk onto a previously stepped on tile, facing 90 degrees left from where they were
+    //      before, with an obstacle in front of them
+    //
+    // Possible solutions:
+    // 1) Brute force - place an obstacle at every step, and re-run the simulation
+    // 2) Walk back:
+    //      a) Start at each existing turning point
+    //      b) Walk backwards, looking for obstacles to the left
+    //      c) If we've already turned at this point, ignore it
+    //      d) If we've not, branch into two and recurse - one turns left, the other continues
+    //      straight
+    //      e) Continue walking backwards until either we reach the map edge (no-viable obstacle
+    //      placements), or we run into a previous step facing left (viable obstacle location).
+    //
+    // Recursing from a point:
+    // Scenario 1: There is no obstacle to our left
+    //      Do nothing
+    // Scenario 2: There is an obstacle to our left
+    //      Turn left, and recurse
+    //
+    // Scenario A: There is nothing behind us
+    //      Move backwards
+    // Scenario B: There is something behind us (obstacle or map edge)
+    //      Terminate
+    //
+    //  Scenario I: We encounter a future step, facing left of us
+    //      Drop an obstacle to our left
+    //  Scenario II:
+
+    debug!(?turning_points);
+
+    turning_points
+        .iter()
+        .filter(|p| {
+            let is_branching_point = p
+                .move_left(map_size)
+                .map(|l| obstacles.contains(&(l.y, l.x)));
+
+            if is_branching_point.is_some_and(|b| b) {
+                trace!(point = ?p, "branch");
+                // todo: if we've not already turned here, branch & recurse - one moving back,
+                // the other turning left
+                !turning_points.contains(&p.turn_left())
+            } else {
+                false
+            }
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(&parse(EXAMPLE).unwrap()), 41);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(&parse(EXAMPLE).unwrap()), 6);
+    }
+
+    #[test]
+    fn unexpected_tile_location() {
+        assert_eq!(
+            parse("...\n.^.\n..O\n").unwrap_err(),
+            ParseError::UnexpectedTile {
+                location: Location::new(2, 2),
+                found: 'O',
+            }
+        );
+        assert_eq!(parse("...\n...\n").unwrap_err(), ParseError::MissingGuard);
+    }
+}
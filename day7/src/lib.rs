@@ -0,0 +1,169 @@
+use aoc_common::Location;
+use thiserror::Error;
+
+/// An operator, giving `None` if the result won't fit
+type Operator = fn(u64, u64) -> Option<u64>;
+
+/// +
+fn add(a: u64, b: u64) -> Option<u64> {
+    a.checked_add(b)
+}
+
+/// *
+fn mul(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(b)
+}
+
+/// ||
+fn concat(a: u64, b: u64) -> Option<u64> {
+    // Calculate how many digits we need to shift left
+    let b_log10 = b.checked_ilog10().map_or(0, |log10| log10 + 1);
+    let mult = 10_u64.checked_pow(b_log10)?;
+
+    a.checked_mul(mult)?.checked_add(b)
+}
+
+/// Checks if it is possible to create the total
+fn check_case(total: u64, operands: &[u64], operators: &[Operator]) -> bool {
+    let Some((&first, rest)) = operands.split_first() else {
+        return false;
+    };
+
+    reaches(total, first, rest, operators)
+}
+
+/// Checks if applying operators to `acc` and each of the rest of the operands in turn can make the
+/// total
+fn reaches(total: u64, acc: u64, rest: &[u64], operators: &[Operator]) -> bool {
+    let Some((&x, rest)) = rest.split_first() else {
+        return acc == total;
+    };
+
+    operators.iter().any(|op| match op(acc, x) {
+        Some(acc) if acc <= total => reaches(total, acc, rest, operators),
+        // Only multiplying by zero makes the accumulator smaller, so once it's past the total the
+        // only way back is wiping it out at the next zero
+        _ => rest.iter().position(|&x| x == 0).is_some_and(|zero| {
+            operators.iter().any(|op| op(u64::MAX, 0) == Some(0))
+                && reaches(total, 0, &rest[zero + 1..], operators)
+        }),
+    })
+}
+
+/// A calibration equation with its operators missing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    pub total: u64,
+    pub operands: Vec<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("{location}: expected ': ' after the total")]
+    MissingSeparator { location: Location },
+    #[error("{location}: expected a number, found {found:?}")]
+    InvalidNumber { location: Location, found: String },
+}
+
+/// Parses a number which was sliced out of the given line
+fn parse_number(line_index: usize, line: &str, token: &str) -> Result<u64, ParseError> {
+    token.parse::<u64>().map_err(|_| ParseError::InvalidNumber {
+        location: Location::of_token(line_index, line, token),
+        found: token.to_string(),
+    })
+}
+
+/// Parses one equation per line
+pub fn parse(input: &str) -> Result<Vec<Equation>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        // Parse out the numbers
+        .map(|(i, l)| {
+            let (a, b) = l.split_once(": ").ok_or_else(|| {
+                // Point at wherever the total stops
+                let end = l.find(|c: char| !c.is_ascii_digit()).unwrap_or(l.len());
+                ParseError::MissingSeparator {
+                    location: Location::of_token(i, l, &l[end..]),
+                }
+            })?;
+            let total = parse_number(i, l, a)?;
+            let operands = b
+                .split(" ")
+                .map(|x| parse_number(i, l, x))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Equation { total, operands })
+        })
+        .collect()
+}
+
+/// Sums up the totals of the equations which can be made with the given operators
+fn calibrate(input: &[Equation], operators: &[Operator]) -> u64 {
+    input
+        .iter()
+        // Filter out only ones that are possible
+        .filter(|e| check_case(e.total, &e.operands, operators))
+        // Sum up the totals
+        .map(|e| e.total)
+        .sum::<u64>()
+}
+
+pub fn solve_part1(input: &[Equation]) -> u64 {
+    calibrate(input, &[add, mul])
+}
+
+pub fn solve_part2(input: &[Equation]) -> u64 {
+    calibrate(input, &[add, mul, concat])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(&parse(EXAMPLE).unwrap()), 3749);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(&parse(EXAMPLE).unwrap()), 11387);
+    }
+
+    #[test]
+    fn overflow_is_pruned() {
+        let big = u64::MAX / 2;
+        let input = parse(&format!(
+            "{big}: {big} 3 1\n{total}: {big} 1\n0: {big} 9 0\n1: {big} 9 0 1\n",
+            total = big + 1
+        ))
+        .unwrap();
+        // Going past the total can still come back down to it by multiplying by zero
+        assert_eq!(solve_part1(&input), big + 2);
+        assert_eq!(solve_part2(&input), big + 2);
+
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(12, 0), Some(12));
+        assert_eq!(concat(u64::MAX / 10, 99), None);
+    }
+
+    #[test]
+    fn error_locations() {
+        assert_eq!(
+            parse("190: 10 19\n3267 81 40 27\n").unwrap_err(),
+            ParseError::MissingSeparator {
+                location: Location::new(1, 4),
+            }
+        );
+        assert_eq!(
+            parse("190: 10 19\n3267: 81 4x 27\n").unwrap_err(),
+            ParseError::InvalidNumber {
+                location: Location::new(1, 9),
+                found: "4x".to_string(),
+            }
+        );
+    }
+}
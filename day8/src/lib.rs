@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use aoc_common::{Grid, GridError, Vec2};
+use itertools::Itertools;
+use thiserror::Error;
+use tracing::debug;
+
+/// The roof map with the antennae on it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    /// (height, width)
+    pub size: (usize, usize),
+    /// Antenna locations, partitioned by their frequency
+    pub antennae: HashMap<char, Vec<Vec2>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+}
+
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    // Read input & get map size
+    let grid = Grid::parse(input, |c| c)?;
+
+    // Parse the antennae into their locations, partitioned by their identifier
+    let antennae = grid
+        .iter()
+        .filter(|(_, c)| **c != '.')
+        // Create Vec2 objects
+        .map(|(pos, c)| (*c, Vec2::from(pos)))
+        // Collect into frequency -> [location] map
+        .fold(HashMap::<_, Vec<_>>::new(), |mut hm, (c, pos)| {
+            hm.entry(c).or_default().push(pos);
+            hm
+        });
+
+    Ok(Map {
+        size: grid.size(),
+        antennae,
+    })
+}
+
+pub fn solve_part1(map: &Map) -> usize {
+    let map_size = map.size;
+
+    // Generate the antinodes for each pair of antennae
+    let antinodes = map
+        .antennae
+        .iter()
+        .map(|(c, positions)| {
+            let antinodes = positions
+                .iter()
+                .permutations(2)
+                .map(|points| *points[1] + *points[1] - *points[0])
+                .filter(|p| p.is_within(map_size))
+                .collect::<Vec<_>>();
+
+            (c, antinodes)
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Count how many unique antinodes there are
+    let mut unique_antinodes = antinodes.values().flatten().cloned().collect::<Vec<_>>();
+    unique_antinodes.sort();
+    unique_antinodes.dedup();
+
+    unique_antinodes.len()
+}
+
+/// Geneerates all the antinodes for two antennae in a line
+fn generate_antinodes(p1: Vec2, p2: Vec2, map_bounds: (usize, usize)) -> Vec<Vec2> {
+    let diff = p2 - p1;
+
+    let left = (0..)
+        .map(|distance| p2 + diff * distance)
+        .take_while(|p| p.is_within(map_bounds));
+
+    let right = (0..)
+        .map(|distance| p1 - diff * distance)
+        .take_while(|p| p.is_within(map_bounds));
+
+    left.chain(right).collect()
+}
+
+pub fn solve_part2(map: &Map) -> usize {
+    let map_size = map.size;
+    debug!(?map_size);
+
+    // Generate the antinodes for each pair of antennae
+    let antinodes = map
+        .antennae
+        .iter()
+        .map(|(c, positions)| {
+            // Iterate over each pair of antennae
+            let antinodes = positions
+                .iter()
+                .combinations(2)
+                // Generate all the antinodes
+                .flat_map(|points| generate_antinodes(*points[0], *points[1], map_size))
+                .collect::<Vec<_>>();
+
+            (c, antinodes)
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Count how many unique antinodes there are
+    let mut unique_antinodes = antinodes.values().flatten().cloned().collect::<Vec<_>>();
+    unique_antinodes.sort();
+    unique_antinodes.dedup();
+
+    unique_antinodes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Location;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(&parse(EXAMPLE).unwrap()), 14);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(&parse(EXAMPLE).unwrap()), 34);
+    }
+
+    #[test]
+    fn ragged_row_location() {
+        assert_eq!(
+            parse("..a.\n....\n..a..\n").unwrap_err(),
+            ParseError::Grid(GridError::RaggedRow {
+                location: Location::new(2, 4),
+                expected: 4,
+                found: 5,
+            })
+        );
+    }
+}
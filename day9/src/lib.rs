@@ -0,0 +1,245 @@
+use aoc_common::Location;
+use thiserror::Error;
+use tracing::debug;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Block {
+    Empty { length: usize },
+    File { id: usize, length: usize },
+}
+
+struct Blockerator<'a> {
+    inner: Box<dyn Iterator<Item = (usize, usize)> + 'a>, // (id, length)
+    current: (usize, usize),                              // (id, length)
+}
+
+impl<'a> Blockerator<'a> {
+    fn new(blocks: &'a [Block]) -> Self {
+        Self {
+            inner: Box::new(blocks.iter().rev().filter_map(|b| match *b {
+                Block::File { id, length } => Some((id, length)),
+                Block::Empty { .. } => None,
+            })),
+            current: (0, 0),
+        }
+    }
+
+    /// Take a bunch of blocks off the end of the stack, until the requested amount of bytes are
+    /// fulfilled or there's no files left to take
+    fn take(&mut self, mut bytes: usize) -> Vec<Block> {
+        let mut out = vec![];
+        while bytes > 0 {
+            // Refresh the current block
+            if self.current.1 == 0 {
+                let Some(next) = self.inner.next() else {
+                    break;
+                };
+                self.current = next;
+                continue;
+            }
+
+            let bytes_taken = self.current.1.min(bytes);
+
+            out.push(Block::File {
+                id: self.current.0,
+                length: bytes_taken,
+            });
+
+            self.current.1 -= bytes_taken;
+            bytes -= bytes_taken;
+        }
+
+        out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("{location}: expected a digit, found {found:?}")]
+    InvalidDigit { location: Location, found: char },
+}
+
+/// Parses the disk map into a list of blocks
+pub fn parse(input: &str) -> Result<Vec<Block>, ParseError> {
+    // Offsets are kept relative to the untrimmed input, so errors point at the right place
+    let start = input.len() - input.trim_start().len();
+    let digits = input
+        .trim()
+        .char_indices()
+        // Parse inputs as dgits
+        .map(|(i, c)| {
+            c.to_digit(10).ok_or_else(|| ParseError::InvalidDigit {
+                location: Location::of_offset(input, start + i),
+                found: c,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (_, parsed) = digits
+        .into_iter()
+        .enumerate()
+        // Convert inputs into structured blocks
+        .fold((0, vec![]), |(mut id, mut parsed), (i, val)| {
+            if i % 2 == 0 {
+                // file
+                parsed.push(Block::File {
+                    id,
+                    length: val as usize,
+                });
+                id += 1;
+            } else {
+                // not a file
+                parsed.push(Block::Empty {
+                    length: val as usize,
+                })
+            }
+
+            (id, parsed)
+        });
+
+    Ok(parsed)
+}
+
+pub fn solve_part1(parsed: &[Block]) -> usize {
+    debug!(
+        blocks = parsed.len(),
+        first = ?&parsed[..parsed.len().min(10)],
+        last = ?&parsed[parsed.len().saturating_sub(10)..],
+    );
+
+    let compressed_length = parsed
+        .iter()
+        .filter_map(|b| match b {
+            Block::File { length, .. } => Some(*length),
+            _ => None,
+        })
+        .sum::<usize>();
+
+    debug!(compressed_length);
+
+    let mut blockerator = Blockerator::new(parsed);
+
+    let (_, checksum) = parsed
+        .iter()
+        // Replace empty blocks with files from the end
+        .flat_map(|b| match b {
+            Block::Empty { length } => blockerator.take(*length),
+            Block::File { .. } => vec![*b],
+        })
+        .filter_map(|b| match b {
+            Block::File { id, length } => Some((id, length)),
+            Block::Empty { .. } => None,
+        })
+        .fold((0, 0), |(mut total_bytes, mut checksum), (id, length)| {
+            // If we've already processed enough files, no-op for the rest
+            if total_bytes >= compressed_length {
+                return (total_bytes, checksum);
+            }
+
+            // Calculate the checksum
+            let length = length.min(compressed_length - total_bytes);
+            checksum += (total_bytes..total_bytes + length)
+                .map(|pos| pos * id)
+                .sum::<usize>();
+            total_bytes += length;
+
+            (total_bytes, checksum)
+        });
+
+    checksum
+}
+
+/// Moves whole files into the leftmost gap they fit in, going through the files from the end
+pub fn solve_part2(parsed: &[Block]) -> usize {
+    // Lay the blocks out on the disk as (start, length) spans
+    let mut files = vec![];
+    let mut gaps = vec![];
+    parsed.iter().fold(0, |start, b| match *b {
+        Block::File { id, length } => {
+            files.push((id, start, length));
+            start + length
+        }
+        Block::Empty { length } => {
+            gaps.push((start, length));
+            start + length
+        }
+    });
+
+    // Move each file once, starting from the highest ID
+    for (_, file_start, file_length) in files.iter_mut().rev() {
+        // Only gaps to the left of the file are fair game
+        let gap = gaps
+            .iter_mut()
+            .take_while(|(gap_start, _)| gap_start < file_start)
+            .find(|(_, gap_length)| gap_length >= file_length);
+
+        if let Some((gap_start, gap_length)) = gap {
+            *file_start = *gap_start;
+            *gap_start += *file_length;
+            *gap_length -= *file_length;
+        }
+    }
+
+    // Calculate the checksum
+    files
+        .iter()
+        .map(|&(id, start, length)| (start..start + length).map(|pos| pos * id).sum::<usize>())
+        .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(&parse(EXAMPLE).unwrap()), 1928);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(&parse(EXAMPLE).unwrap()), 2858);
+    }
+
+    #[test]
+    fn trailing_gap_bigger_than_files() {
+        // Gaps at the end can outlast every file left to move into them
+        assert_eq!(solve_part1(&parse("19").unwrap()), 0);
+        assert_eq!(solve_part1(&parse("191").unwrap()), 1);
+        assert_eq!(solve_part1(&parse("285").unwrap()), 20);
+        assert_eq!(solve_part2(&parse("285").unwrap()), 20);
+    }
+
+    #[test]
+    fn every_disk_map_solves() {
+        // Every map up to 4 digits, so every mix of short files and long gaps
+        for n in 0..10_000 {
+            for map in [format!("{}", n), format!("{:04}", n)] {
+                let blocks = parse(&map).unwrap();
+                solve_part1(&blocks);
+                solve_part2(&blocks);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_digit_location() {
+        // Leading whitespace is skipped, but still counts towards the column
+        assert_eq!(
+            parse("  12x").unwrap_err(),
+            ParseError::InvalidDigit {
+                location: Location::new(0, 4),
+                found: 'x',
+            }
+        );
+        assert_eq!(
+            parse("\n123\n45").unwrap_err(),
+            ParseError::InvalidDigit {
+                location: Location::new(1, 3),
+                found: '\n',
+            }
+        );
+    }
+}